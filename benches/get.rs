@@ -0,0 +1,40 @@
+//! Compares [`BlazeMap::get`] with [`BlazeMap::get_checked_capacity`].
+#![allow(missing_docs)]
+
+use blazemap::{define_plain_id, prelude::BlazeMap};
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+
+define_plain_id! {
+    struct Id
+}
+
+const NUM_KEYS: usize = 10_000;
+
+fn get(c: &mut Criterion) {
+    let keys: Vec<_> = Id::new_n(NUM_KEYS + NUM_KEYS / 10).collect();
+    let mut map = BlazeMap::new();
+    for (value, key) in keys.iter().take(NUM_KEYS).step_by(2).enumerate() {
+        map.insert(*key, value);
+    }
+    let sum = |lookup: fn(&BlazeMap<Id, usize>, Id) -> Option<&usize>| {
+        keys.iter()
+            .filter_map(|key| lookup(black_box(&map), *key))
+            .sum::<usize>()
+    };
+    assert_eq!(
+        sum(BlazeMap::get),
+        sum(BlazeMap::get_checked_capacity),
+        "`get_checked_capacity` must produce the same results as `get`"
+    );
+
+    let mut group = c.benchmark_group("get");
+    group.bench_function("get", |b| b.iter(|| sum(BlazeMap::get)));
+    group.bench_function("get_checked_capacity", |b| {
+        b.iter(|| sum(BlazeMap::get_checked_capacity));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, get);
+criterion_main!(benches);
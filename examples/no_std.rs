@@ -0,0 +1,33 @@
+//! Checks that `blazemap` collections and plain identifiers
+//! can be used from a `#![no_std]` crate that only relies on `alloc`.
+//!
+//! Build with `cargo build --example no_std --no-default-features`.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use blazemap::{define_plain_id, prelude::BlazeMap};
+
+define_plain_id! {
+    /// Identifier of a sensor.
+    pub struct SensorId;
+    Derive: {
+        Ord
+    }
+}
+
+/// Registers `n` sensors and returns the sum of their readings
+/// along with their identifiers.
+#[must_use]
+pub fn total_reading(n: u32) -> (u32, Vec<SensorId>) {
+    let mut readings = BlazeMap::new();
+    let mut sensors = Vec::new();
+    for reading in 0..n {
+        let sensor = SensorId::new();
+        readings.insert(sensor, reading);
+        sensors.push(sensor);
+    }
+    (readings.values().sum(), sensors)
+}
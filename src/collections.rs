@@ -1,2 +1,4 @@
 /// Defines [`BlazeMap`](crate::prelude::BlazeMap).
 pub mod map;
+/// Defines [`BlazeSet`](crate::prelude::BlazeSet).
+pub mod set;
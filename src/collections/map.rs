@@ -1,38 +1,60 @@
 #![allow(clippy::module_name_repetitions)]
 
+#[cfg(feature = "rkyv")]
+pub use crate::collections::map::archived::{ArchivedBlazeMap, BlazeMapResolver};
+#[cfg(feature = "rayon")]
+pub use crate::collections::map::par_iters::{IntoParIter, ParIter, ParIterMut};
 pub use crate::collections::map::{
-    entries::{Entry, OccupiedEntry, VacantEntry},
-    iters::{Drain, IntoIter, IntoKeys, IntoValues, Iter, IterMut, Keys, Values, ValuesMut},
+    entries::{Entry, OccupiedEntry, TrackedMut, VacantEntry},
+    iters::{
+        Drain, DrainRange, ExtractIf, IntoIter, IntoKeys, IntoValues, Iter, IterMut, Keys, Values,
+        ValuesMut, ValuesMutChunk,
+    },
 };
 use crate::{
-    collections::map::entries::VacantEntryInner,
+    collections::{map::entries::VacantEntryInner, set::BlazeSet},
+    error::CapacityOverflow,
     traits::{
-        BlazeMapId, BlazeMapIdStatic, CapacityInfoProvider, KeyByOffsetProvider, TypeInfoContainer,
+        BlazeMapId, BlazeMapIdStatic, BlazeMapIdWrapper, CapacityInfoProvider, KeyByOffsetProvider,
+        TypeInfoContainer,
     },
 };
-use std::{
+use alloc::{vec, vec::Vec};
+#[cfg(feature = "arbitrary")]
+use arbitrary::{Arbitrary, Unstructured};
+#[cfg(feature = "borsh")]
+use borsh::{BorshDeserialize, BorshSerialize};
+use core::{
     borrow::Borrow,
     fmt::{Debug, Formatter},
     marker::PhantomData,
+    ops::Range,
+};
+#[cfg(feature = "rayon")]
+use rayon::{
+    iter::{IndexedParallelIterator, IntoParallelIterator, ParallelExtend, ParallelIterator},
+    slice::ParallelSliceMut,
 };
 #[cfg(feature = "serde")]
-use {
-    crate::prelude::BlazeMapIdWrapper,
-    serde::{
-        de::{MapAccess, Visitor},
-        ser::SerializeMap,
-        Deserialize, Deserializer, Serialize, Serializer,
-    },
+use serde::{
+    de::{MapAccess, SeqAccess, Visitor},
+    ser::{SerializeMap, SerializeSeq},
+    Deserialize, Deserializer, Serialize, Serializer,
 };
+#[cfg(feature = "std")]
+use std::{collections::HashMap, hash::BuildHasher};
 
+#[cfg(feature = "rkyv")]
+mod archived;
 mod entries;
 mod iters;
+#[cfg(feature = "rayon")]
+mod par_iters;
 
 /// A [`Vec`]-based analogue of a [`HashMap`](std::collections::HashMap).
-#[derive(Clone, PartialEq, Eq)]
 pub struct BlazeMap<K, V> {
-    pub(in crate::collections::map) inner: Vec<Option<V>>,
-    pub(in crate::collections::map) len: usize,
+    pub(in crate::collections) inner: Vec<Option<V>>,
+    pub(in crate::collections) len: usize,
     phantom: PhantomData<K>,
 }
 
@@ -70,6 +92,25 @@ impl<K, V> BlazeMap<K, V> {
         self.len = 0;
     }
 
+    /// Retains only the entries whose keys are contained in `allowed`,
+    /// in a single pass over the map without reconstructing the keys.
+    #[inline]
+    pub fn retain_in_set(&mut self, allowed: &BlazeSet<K>) {
+        debug_assert_eq!(
+            self.inner.iter().filter_map(Option::as_ref).count(),
+            self.len
+        );
+        let covered_len = self.inner.len().min(allowed.bitmask.len() * 8);
+        self.inner.truncate(covered_len);
+        for (offset, value) in self.inner.iter_mut().enumerate() {
+            let byte = unsafe { allowed.bitmask.get_unchecked(offset / 8) };
+            if byte & (1 << (offset % 8)) == 0 {
+                *value = None;
+            }
+        }
+        self.len = self.inner.iter().filter(|value| value.is_some()).count();
+    }
+
     /// Shrinks the capacity of the map as much as possible.
     /// It will drop down as much as possible while maintaining the internal
     /// rules and possibly leaving some space in accordance with the resize
@@ -93,6 +134,124 @@ impl<K, V> BlazeMap<K, V> {
         debug_assert_eq!(current_len, self.len);
     }
 
+    /// Shrinks the capacity of the map with a lower limit.
+    /// It will drop down no lower than the supplied limit while maintaining
+    /// the internal rules and possibly leaving some space in accordance with
+    /// the resize policy.
+    ///
+    /// If the current capacity is less than the lower limit, this is a no-op.
+    #[inline]
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        let prev_len = self.inner.iter().filter_map(Option::as_ref).count();
+        debug_assert_eq!(prev_len, self.len);
+        let elems_to_crop = self
+            .inner
+            .iter()
+            .rev()
+            .position(Option::is_some)
+            .unwrap_or(self.inner.len());
+        self.inner.truncate(self.inner.len() - elems_to_crop);
+        self.inner.shrink_to(min_capacity);
+        let current_len = self.inner.iter().filter_map(Option::as_ref).count();
+        debug_assert_eq!(prev_len, current_len);
+        debug_assert_eq!(current_len, self.len);
+    }
+
+    /// Moves all the entries from `other` into `self`, leaving `other` empty.
+    /// Keeps the allocated memory of `other` for reuse.
+    ///
+    /// If a key from `other` is already present in `self`,
+    /// the respective value from `self` is overwritten
+    /// with the respective value from `other`.
+    #[inline]
+    pub fn append(&mut self, other: &mut Self) {
+        debug_assert_eq!(
+            self.inner.iter().filter_map(Option::as_ref).count(),
+            self.len
+        );
+        debug_assert_eq!(
+            other.inner.iter().filter_map(Option::as_ref).count(),
+            other.len
+        );
+        if self.inner.len() < other.inner.len() {
+            self.inner.resize_with(other.inner.len(), || None);
+        }
+        for (dst, src) in self.inner.iter_mut().zip(&mut other.inner) {
+            if let Some(value) = src.take() {
+                if dst.replace(value).is_none() {
+                    self.len += 1;
+                }
+            }
+        }
+        other.clear();
+    }
+
+    /// An iterator visiting all occupied offsets along with their values
+    /// in ascending offset order. The iterator element type is
+    /// `(usize, &V)`.
+    ///
+    /// Unlike [`iter`](BlazeMap::iter), it doesn't reconstruct the keys,
+    /// so it doesn't require `K` to be a [`BlazeMapId`] type.
+    #[inline]
+    pub fn iter_offsets(&self) -> impl Iterator<Item = (usize, &V)> {
+        debug_assert_eq!(
+            self.inner.iter().filter_map(Option::as_ref).count(),
+            self.len
+        );
+        self.inner
+            .iter()
+            .enumerate()
+            .filter_map(|(offset, value)| Some((offset, value.as_ref()?)))
+    }
+
+    /// Creates a new map with the same keys
+    /// and the values obtained by applying `f` to the values of `self`.
+    ///
+    /// The offsets of the keys are preserved,
+    /// so no key reconstruction or entry lookup is involved.
+    #[inline]
+    #[must_use]
+    pub fn map_values<U, F>(&self, mut f: F) -> BlazeMap<K, U>
+    where
+        F: FnMut(&V) -> U,
+    {
+        debug_assert_eq!(
+            self.inner.iter().filter_map(Option::as_ref).count(),
+            self.len
+        );
+        BlazeMap {
+            inner: self
+                .inner
+                .iter()
+                .map(|value| value.as_ref().map(&mut f))
+                .collect(),
+            len: self.len,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Converts the map into a new one with the same keys
+    /// and the values obtained by applying `f` to the values of `self`.
+    ///
+    /// See [`map_values`](Self::map_values) for the borrowing version.
+    #[inline]
+    #[must_use]
+    pub fn into_map_values<U, F>(self, mut f: F) -> BlazeMap<K, U>
+    where
+        F: FnMut(V) -> U,
+    {
+        debug_assert_eq!(
+            self.inner.iter().filter_map(Option::as_ref).count(),
+            self.len
+        );
+        let Self { inner, len, .. } = self;
+        BlazeMap {
+            inner: inner.into_iter().map(|value| value.map(&mut f)).collect(),
+            len,
+            phantom: PhantomData,
+        }
+    }
+
     /// Clears the map, returning all key-value pairs as an iterator.
     /// Keeps the allocated memory for reuse.
     ///
@@ -108,16 +267,57 @@ impl<K, V> BlazeMap<K, V> {
             self.len
         );
         Drain {
+            end_position: self.inner.len(),
             map: self,
             current_position: 0,
         }
     }
+
+    /// Folds every value of the map into an accumulator
+    /// in ascending offset order.
+    ///
+    /// Equivalent to `self.values().fold(init, f)`, but walks the underlying
+    /// storage directly without reconstructing the keys.
+    #[inline]
+    pub fn fold_values<B, F>(&self, init: B, f: F) -> B
+    where
+        F: FnMut(B, &V) -> B,
+    {
+        debug_assert_eq!(
+            self.inner.iter().filter_map(Option::as_ref).count(),
+            self.len
+        );
+        self.inner.iter().flatten().fold(init, f)
+    }
 }
 
 impl<K, V> BlazeMap<K, V>
 where
     K: BlazeMapId,
 {
+    /// Removes the entries whose key offsets fall within `range`,
+    /// returning them as an iterator.
+    ///
+    /// The range is clamped to the length of the backing storage.
+    /// If the returned iterator is dropped before being fully consumed,
+    /// it drops the remaining entries of the range. If the range covers
+    /// the tail of the backing storage, the trailing vacant slots are
+    /// truncated as well.
+    #[inline]
+    pub fn drain_range(&mut self, range: Range<usize>) -> DrainRange<'_, K, V> {
+        debug_assert_eq!(
+            self.inner.iter().filter_map(Option::as_ref).count(),
+            self.len
+        );
+        let end_position = range.end.min(self.inner.len());
+        let current_position = range.start.min(end_position);
+        DrainRange {
+            map: self,
+            current_position,
+            end_position,
+        }
+    }
+
     /// An iterator visiting all key-value pairs. The iterator element type is
     /// `(K, &V)`.
     #[inline]
@@ -163,6 +363,13 @@ where
         Keys { inner: self.iter() }
     }
 
+    /// Returns a [`BlazeSet`] containing all the keys of the map.
+    #[inline]
+    #[must_use]
+    pub fn key_set(&self) -> BlazeSet<K> {
+        BlazeSet::from(self)
+    }
+
     /// An iterator visiting all values. The iterator element type is `&V`.
     #[inline]
     #[must_use]
@@ -187,6 +394,42 @@ where
             inner: self.iter_mut(),
         }
     }
+
+    /// Splits the offset space of the map into `n` contiguous chunks,
+    /// each being a mutable iterator over the values of its portion.
+    ///
+    /// Since the chunks cover disjoint offset ranges, they can be handed
+    /// over to different threads, e.g. via [`std::thread::scope`].
+    /// Exactly `n` chunks are returned; some of them may be empty.
+    ///
+    /// # Panics
+    /// Panics if `n` is zero.
+    #[inline]
+    #[must_use]
+    pub fn value_chunks_mut(&mut self, n: usize) -> Vec<ValuesMutChunk<'_, K, V>> {
+        debug_assert_eq!(
+            self.inner.iter().filter_map(Option::as_ref).count(),
+            self.len
+        );
+        assert_ne!(n, 0, "number of chunks must be non-zero");
+        let chunk_size = self.inner.len().div_ceil(n);
+        let mut rest = self.inner.as_mut_slice();
+        let mut base_offset = 0;
+        (0..n)
+            .map(|_| {
+                let mid = chunk_size.min(rest.len());
+                let (chunk, tail) = core::mem::take(&mut rest).split_at_mut(mid);
+                rest = tail;
+                let chunk = ValuesMutChunk {
+                    inner: chunk.iter_mut(),
+                    base_offset,
+                    phantom: PhantomData,
+                };
+                base_offset += chunk_size;
+                chunk
+            })
+            .collect()
+    }
 }
 
 impl<K, V> BlazeMap<K, V>
@@ -207,6 +450,180 @@ where
             phantom: PhantomData,
         }
     }
+
+    /// Creates a new instance of [`BlazeMap`]
+    /// containing every currently registered `K` instance as a key,
+    /// with the values computed by `f` from the respective keys.
+    #[inline]
+    #[must_use]
+    pub fn from_keys_with<F>(mut f: F) -> Self
+    where
+        F: FnMut(K) -> V,
+    {
+        let keys = K::all_instances_iter();
+        let len = keys.len();
+        let mut inner = Vec::with_capacity(keys.range.end);
+        inner.resize_with(keys.range.start, || None);
+        inner.extend(keys.map(|key| Some(f(key))));
+        Self {
+            inner,
+            len,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Inserts the values at the consecutive offsets starting from
+    /// `start_offset`, growing the backing storage at most once
+    /// (as long as the size hint of `values` is exact).
+    ///
+    /// Existing entries within the range are overwritten.
+    ///
+    /// # Panics
+    /// Panics if any of the offsets doesn't correspond to a registered `K`
+    /// instance. The offsets covered by the lower bound of the size hint of
+    /// `values` are checked before anything is inserted, so exact-size
+    /// inputs leave the map untouched on failure. For the values beyond
+    /// that bound, the ones preceding the offending value are inserted anyway.
+    #[inline]
+    pub fn insert_many_dense(&mut self, start_offset: usize, values: impl IntoIterator<Item = V>) {
+        debug_assert_eq!(
+            self.inner.iter().filter_map(Option::as_ref).count(),
+            self.len
+        );
+        let registered = K::all_instances_iter().range;
+        assert!(
+            start_offset >= registered.start,
+            "BlazeMap offset {start_offset} isn't registered"
+        );
+        let values = values.into_iter();
+        let checked_end = start_offset
+            .checked_add(values.size_hint().0)
+            .filter(|&end_offset| end_offset <= registered.end)
+            .unwrap_or_else(|| {
+                panic!(
+                    "BlazeMap offset {} isn't registered",
+                    registered.end.max(start_offset)
+                )
+            });
+        if checked_end > self.inner.len() {
+            self.inner.reserve(checked_end - self.inner.len());
+        }
+        if start_offset > self.inner.len() {
+            self.inner.resize_with(start_offset, || None);
+        }
+        for (offset, value) in (start_offset..).zip(values) {
+            if offset >= checked_end {
+                assert!(
+                    offset < registered.end,
+                    "BlazeMap offset {offset} isn't registered"
+                );
+            }
+            if let Some(slot) = self.inner.get_mut(offset) {
+                if slot.replace(value).is_none() {
+                    self.len += 1;
+                }
+            } else {
+                self.inner.push(Some(value));
+                self.len += 1;
+            }
+        }
+        debug_assert_eq!(
+            self.inner.iter().filter_map(Option::as_ref).count(),
+            self.len
+        );
+    }
+
+    /// An iterator visiting all entries as pairs of the original keys and the
+    /// values, in ascending offset order.
+    ///
+    /// Takes a single read guard on the `K` registry up front instead of
+    /// locking it per element, and holds it for the whole lifetime of the
+    /// iterator (for the types that have one), so registering new `K`
+    /// instances while iterating may deadlock. The original keys are cloned
+    /// out of the registry.
+    #[inline]
+    pub fn iter_with_keys(&self) -> impl Iterator<Item = (impl Borrow<K::OrigType>, &V)> + '_ {
+        debug_assert_eq!(
+            self.inner.iter().filter_map(Option::as_ref).count(),
+            self.len
+        );
+        let guard = K::static_container().key_by_offset_provider();
+        self.inner
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, value)| Some((idx, value.as_ref()?)))
+            .map(move |(idx, value)| {
+                let key = unsafe { guard.key_by_offset_unchecked(idx) };
+                (key.borrow().clone(), value)
+            })
+    }
+
+    /// An iterator visiting all entries as pairs of the original keys and
+    /// mutable references to the values, in ascending offset order.
+    ///
+    /// Takes a single read guard on the `K` registry up front, in the same way
+    /// as [`iter_with_keys`](Self::iter_with_keys) does. The registry is
+    /// separate from the map, so the guard is held alongside the mutable
+    /// borrow of the values.
+    #[inline]
+    pub fn iter_mut_with_keys(
+        &mut self,
+    ) -> impl Iterator<Item = (impl Borrow<K::OrigType>, &mut V)> + '_ {
+        debug_assert_eq!(
+            self.inner.iter().filter_map(Option::as_ref).count(),
+            self.len
+        );
+        let guard = K::static_container().key_by_offset_provider();
+        self.inner
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(idx, value)| Some((idx, value.as_mut()?)))
+            .map(move |(idx, value)| {
+                let key = unsafe { guard.key_by_offset_unchecked(idx) };
+                (key.borrow().clone(), value)
+            })
+    }
+
+    /// Converts the map into a [`BlazeMap`] keyed by another key type
+    /// sharing the same original key type.
+    ///
+    /// The original key of each entry is resolved in the `K` registry
+    /// and then registered in the `B` registry.
+    /// The `K` registry lock is released before any `B` key is registered,
+    /// so `B` may be the same type as `K`.
+    #[inline]
+    #[must_use]
+    pub fn remap_keys<B>(self) -> BlazeMap<B, V>
+    where
+        B: BlazeMapIdWrapper + BlazeMapIdStatic<OrigType = K::OrigType>,
+    {
+        debug_assert_eq!(
+            self.inner.iter().filter_map(Option::as_ref).count(),
+            self.len
+        );
+        let guard = K::static_container().key_by_offset_provider();
+        let entries: Vec<(K::OrigType, V)> = self
+            .inner
+            .into_iter()
+            .enumerate()
+            .filter_map(|(idx, value)| {
+                let key = unsafe { guard.key_by_offset_unchecked(idx) };
+                Some((key.borrow().clone(), value?))
+            })
+            .collect();
+        drop(guard);
+
+        let mut result = BlazeMap::with_current_key_type_capacity();
+        for (key, value) in entries {
+            let key = unsafe { B::new(B::static_container(), key) };
+            result.insert(key, value);
+        }
+        debug_assert_eq!(
+            result.inner.iter().filter_map(Option::as_ref).count(),
+            result.len
+        );
+        result
+    }
 }
 
 impl<K, V> BlazeMap<K, V>
@@ -238,6 +655,48 @@ where
         self.inner.get(key.get_offset()).and_then(Option::as_ref)
     }
 
+    /// Returns `true` if the map contains a value for the key
+    /// with the specified offset.
+    ///
+    /// Useful when only the bare offset is at hand,
+    /// e.g. when it was recovered from an external source.
+    #[inline]
+    #[must_use]
+    pub fn contains_offset(&self, offset: usize) -> bool {
+        self.get_by_offset(offset).is_some()
+    }
+
+    /// Returns a reference to the value corresponding to the key
+    /// with the specified offset.
+    #[inline]
+    #[must_use]
+    pub fn get_by_offset(&self, offset: usize) -> Option<&V> {
+        debug_assert_eq!(
+            self.inner.iter().filter_map(Option::as_ref).count(),
+            self.len
+        );
+        self.inner.get(offset).and_then(Option::as_ref)
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// Produces the same results as [`get`](Self::get), but doesn't run the
+    /// invariant check that [`get`](Self::get) performs in debug builds.
+    /// That check scans the whole backing storage on every call, so in debug
+    /// builds this method is much faster for hot lookup loops over large
+    /// maps. In release builds both methods compile to the same single
+    /// bounds check, as shown by the `get` benchmark.
+    #[inline]
+    #[must_use]
+    pub fn get_checked_capacity(&self, key: K) -> Option<&V> {
+        let offset = key.get_offset();
+        if offset < self.inner.len() {
+            unsafe { self.inner.get_unchecked(offset) }.as_ref()
+        } else {
+            None
+        }
+    }
+
     /// Returns a mutable reference to the value corresponding to the key.
     #[inline]
     #[must_use]
@@ -251,6 +710,74 @@ where
             .and_then(Option::as_mut)
     }
 
+    /// Returns a mutable reference to the value corresponding to the key,
+    /// leaving the map unmodified if the key is absent.
+    ///
+    /// # Errors
+    /// Returns the key back if the map doesn't contain it.
+    #[inline]
+    pub fn get_mut_or_key(&mut self, key: K) -> Result<&mut V, K> {
+        debug_assert_eq!(
+            self.inner.iter().filter_map(Option::as_ref).count(),
+            self.len
+        );
+        self.inner
+            .get_mut(key.get_offset())
+            .and_then(Option::as_mut)
+            .ok_or(key)
+    }
+
+    /// Returns the entry with the lowest offset in the map,
+    /// or `None` if the map is empty.
+    #[inline]
+    #[must_use]
+    pub fn first_key_value(&self) -> Option<(K, &V)> {
+        debug_assert_eq!(
+            self.inner.iter().filter_map(Option::as_ref).count(),
+            self.len
+        );
+        if self.is_empty() {
+            return None;
+        }
+        self.inner.iter().enumerate().find_map(|(offset, value)| {
+            Some((unsafe { K::from_offset_unchecked(offset) }, value.as_ref()?))
+        })
+    }
+
+    /// Returns the entry with the highest offset in the map,
+    /// or `None` if the map is empty.
+    #[inline]
+    #[must_use]
+    pub fn last_key_value(&self) -> Option<(K, &V)> {
+        debug_assert_eq!(
+            self.inner.iter().filter_map(Option::as_ref).count(),
+            self.len
+        );
+        if self.is_empty() {
+            return None;
+        }
+        self.inner
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(offset, value)| {
+                Some((unsafe { K::from_offset_unchecked(offset) }, value.as_ref()?))
+            })
+    }
+
+    /// Returns mutable references to the values corresponding to
+    /// the `N` keys at once, skipping all checks.
+    ///
+    /// # Safety
+    /// Every key must be present in the map, and no two keys may be equal.
+    /// Otherwise, the behavior is undefined.
+    #[inline]
+    #[must_use]
+    pub unsafe fn get_many_unchecked_mut<const N: usize>(&mut self, keys: [K; N]) -> [&mut V; N] {
+        let inner = self.inner.as_mut_ptr();
+        keys.map(|key| (*inner.add(key.get_offset())).as_mut().unwrap_unchecked())
+    }
+
     /// Inserts a key-value pair into the map.
     ///
     /// If the map did not have this key present, None is returned.
@@ -293,19 +820,80 @@ where
         result
     }
 
-    /// Gets the given key’s corresponding entry in the map for in-place
-    /// manipulation.
+    /// Removes and returns the entry with the lowest offset in the map,
+    /// or returns `None` if the map is empty.
     #[inline]
-    #[must_use]
-    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+    pub fn pop_first(&mut self) -> Option<(K, V)> {
         debug_assert_eq!(
             self.inner.iter().filter_map(Option::as_ref).count(),
             self.len
         );
-        let offset = key.get_offset();
-        if offset < self.inner.len() {
-            let value = unsafe { self.inner.get_unchecked_mut(offset) };
-            if value.is_some() {
+        if self.is_empty() {
+            return None;
+        }
+        let (offset, value) = self
+            .inner
+            .iter_mut()
+            .enumerate()
+            .find_map(|(offset, value)| Some((offset, value.take()?)))?;
+        self.len -= 1;
+        debug_assert_eq!(
+            self.inner.iter().filter_map(Option::as_ref).count(),
+            self.len
+        );
+        Some((unsafe { K::from_offset_unchecked(offset) }, value))
+    }
+
+    /// Removes and returns the entry with the highest offset in the map,
+    /// or returns `None` if the map is empty.
+    ///
+    /// The vacant slots left at the end of the map are truncated,
+    /// so that popping repeatedly from the back doesn't rescan them.
+    #[inline]
+    pub fn pop_last(&mut self) -> Option<(K, V)> {
+        debug_assert_eq!(
+            self.inner.iter().filter_map(Option::as_ref).count(),
+            self.len
+        );
+        if self.is_empty() {
+            return None;
+        }
+        let offset = self.inner.iter().rposition(Option::is_some)?;
+        let value = unsafe {
+            self.inner
+                .get_unchecked_mut(offset)
+                .take()
+                .unwrap_unchecked()
+        };
+        let new_len = self.inner[..offset]
+            .iter()
+            .rposition(Option::is_some)
+            .map_or(0, |last_offset| last_offset + 1);
+        self.inner.truncate(new_len);
+        self.len -= 1;
+        debug_assert_eq!(
+            self.inner.iter().filter_map(Option::as_ref).count(),
+            self.len
+        );
+        Some((unsafe { K::from_offset_unchecked(offset) }, value))
+    }
+
+    /// Gets the given key’s corresponding entry in the map for in-place
+    /// manipulation.
+    ///
+    /// No lock of the `K` registry is held while the entry is alive,
+    /// so the callbacks passed to the [`Entry`] methods may register new keys.
+    #[inline]
+    #[must_use]
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        debug_assert_eq!(
+            self.inner.iter().filter_map(Option::as_ref).count(),
+            self.len
+        );
+        let offset = key.get_offset();
+        if offset < self.inner.len() {
+            let value = unsafe { self.inner.get_unchecked_mut(offset) };
+            if value.is_some() {
                 let occupied = OccupiedEntry {
                     key,
                     len: &mut self.len,
@@ -330,6 +918,78 @@ where
         }
     }
 
+    /// Gets the entry corresponding to the key with the given offset
+    /// in the map for in-place manipulation.
+    ///
+    /// Useful in hot loops that already hold the offset,
+    /// since it doesn't require the key to be reconstructed by the caller.
+    ///
+    /// # Safety
+    /// The offset must correspond to a valid registered `K` identifier,
+    /// just like the one passed to
+    /// [`BlazeMapId::from_offset_unchecked`].
+    #[inline]
+    #[must_use]
+    pub unsafe fn entry_at_offset(&mut self, offset: usize) -> Entry<'_, K, V> {
+        self.entry(K::from_offset_unchecked(offset))
+    }
+
+    /// Creates an iterator which uses a closure to determine
+    /// if an entry should be removed.
+    ///
+    /// If the closure returns `true`, the entry is removed from the map
+    /// and yielded. If the closure returns `false`, the entry remains
+    /// in the map and will not be yielded.
+    ///
+    /// If the returned iterator is dropped before being fully consumed,
+    /// it removes the remaining matching entries.
+    #[inline]
+    #[must_use]
+    pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, K, V, F>
+    where
+        F: FnMut(K, &mut V) -> bool,
+    {
+        debug_assert_eq!(
+            self.inner.iter().filter_map(Option::as_ref).count(),
+            self.len
+        );
+        ExtractIf {
+            map: self,
+            pred,
+            current_position: 0,
+        }
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key,
+    /// inserting the result of `f` if the key is not present in the map.
+    /// The function `f` is only called if the key is vacant.
+    #[inline]
+    pub fn get_or_insert_with<F>(&mut self, key: K, f: F) -> &mut V
+    where
+        F: FnOnce() -> V,
+    {
+        self.entry(key).or_insert_with(f)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key,
+    /// inserting the result of `f` if the key is not present in the map.
+    /// The function `f` receives the key and its offset
+    /// and is only called if the key is vacant.
+    #[inline]
+    pub fn get_or_insert_with_key_and_offset<F>(&mut self, key: K, f: F) -> &mut V
+    where
+        F: FnOnce(K, usize) -> V,
+    {
+        self.entry(key).or_insert_with(|| f(key, key.get_offset()))
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key,
+    /// inserting `default` if the key is not present in the map.
+    #[inline]
+    pub fn get_or_insert(&mut self, key: K, default: V) -> &mut V {
+        self.entry(key).or_insert(default)
+    }
+
     /// Creates a consuming iterator visiting all the keys.
     /// The map cannot be used after calling this. The iterator element type is
     /// `K`.
@@ -361,6 +1021,155 @@ where
     }
 }
 
+impl<K, V> BlazeMap<K, V>
+where
+    K: BlazeMapIdWrapper + BlazeMapIdStatic,
+{
+    /// Gets the entry corresponding to the given original key in the map
+    /// for in-place manipulation, registering the key in the `K` registry if
+    /// necessary.
+    ///
+    /// # Panics
+    /// Panics if the registration of a new key exceeds the maximum capacity
+    /// of `K`. See [`try_entry_by_orig_key`](Self::try_entry_by_orig_key)
+    /// for the fallible alternative.
+    #[inline]
+    #[must_use]
+    pub fn entry_by_orig_key(&mut self, key: K::OrigType) -> Entry<'_, K, V> {
+        let key = unsafe { K::new(K::static_container(), key) };
+        self.entry(key)
+    }
+
+    /// Gets the entry corresponding to the given original key in the map
+    /// for in-place manipulation, registering the key in the `K` registry if
+    /// necessary.
+    ///
+    /// # Errors
+    /// Returns [`CapacityOverflow`] if the registration of a new key would
+    /// exceed the maximum capacity of `K`. In this case the map is left
+    /// unchanged.
+    #[inline]
+    pub fn try_entry_by_orig_key(
+        &mut self,
+        key: K::OrigType,
+    ) -> Result<Entry<'_, K, V>, CapacityOverflow> {
+        let key = unsafe { K::try_new(K::static_container(), key) }?;
+        Ok(self.entry(key))
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<K, V> BlazeMap<K, V>
+where
+    K: BlazeMapId + Send,
+    V: Send,
+{
+    /// Extends the map with the key-value pairs of a parallel iterator.
+    ///
+    /// The pairs are first collected into per-thread parts,
+    /// which are then bucketed by disjoint offset ranges
+    /// and scattered into the map in parallel.
+    /// If the iterator yields the same key several times,
+    /// the value that comes last in the iterator order wins,
+    /// just like with sequential insertion.
+    ///
+    /// # Panics
+    /// Panics if the offset of any key exceeds the maximum capacity of the
+    /// map.
+    #[inline]
+    pub fn par_extend<I>(&mut self, par_iter: I)
+    where
+        I: IntoParallelIterator<Item = (K, V)>,
+    {
+        debug_assert_eq!(
+            self.inner.iter().filter_map(Option::as_ref).count(),
+            self.len
+        );
+        let parts: Vec<Vec<(K, V)>> = par_iter
+            .into_par_iter()
+            .collect_vec_list()
+            .into_iter()
+            .collect();
+        let Some(max_offset) = parts
+            .iter()
+            .flatten()
+            .map(|(key, _)| key.get_offset())
+            .max()
+        else {
+            return;
+        };
+        let max_len = isize::MAX as usize / core::mem::size_of::<Option<V>>().max(1);
+        assert!(
+            max_offset < max_len,
+            "BlazeMap offset {max_offset} exceeds maximum capacity"
+        );
+        if self.inner.len() <= max_offset {
+            self.inner.resize_with(max_offset + 1, || None);
+        }
+
+        let chunk_size = self.inner.len().div_ceil(rayon::current_num_threads());
+        let num_chunks = self.inner.len().div_ceil(chunk_size);
+        let bucketed_parts: Vec<Vec<Vec<(usize, V)>>> = parts
+            .into_par_iter()
+            .map(|part| {
+                let mut buckets: Vec<Vec<(usize, V)>> =
+                    core::iter::repeat_with(Vec::new).take(num_chunks).collect();
+                for (key, value) in part {
+                    let offset = key.get_offset();
+                    buckets[offset / chunk_size].push((offset % chunk_size, value));
+                }
+                buckets
+            })
+            .collect();
+        let mut buckets_by_chunk: Vec<Vec<Vec<(usize, V)>>> =
+            core::iter::repeat_with(Vec::new).take(num_chunks).collect();
+        for buckets in bucketed_parts {
+            for (chunk_buckets, bucket) in buckets_by_chunk.iter_mut().zip(buckets) {
+                chunk_buckets.push(bucket);
+            }
+        }
+
+        let added: usize = self
+            .inner
+            .par_chunks_mut(chunk_size)
+            .zip(buckets_by_chunk)
+            .map(|(chunk, buckets)| {
+                let mut added = 0;
+                for (local_offset, value) in buckets.into_iter().flatten() {
+                    if chunk[local_offset].replace(value).is_none() {
+                        added += 1;
+                    }
+                }
+                added
+            })
+            .sum();
+        self.len += added;
+        debug_assert_eq!(
+            self.inner.iter().filter_map(Option::as_ref).count(),
+            self.len
+        );
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<K, V> ParallelExtend<(K, V)> for BlazeMap<K, V>
+where
+    K: BlazeMapId + Send,
+    V: Send,
+{
+    /// Extends the map with the key-value pairs of a parallel iterator.
+    ///
+    /// See [`BlazeMap::par_extend`] for the details and for the overwrite
+    /// semantics for duplicate keys.
+    #[inline]
+    fn par_extend<I>(&mut self, par_iter: I)
+    where
+        I: IntoParallelIterator<Item = (K, V)>,
+    {
+        BlazeMap::par_extend(self, par_iter);
+    }
+}
+
 impl<K, V> IntoIterator for BlazeMap<K, V>
 where
     K: BlazeMapId,
@@ -430,17 +1239,123 @@ where
     }
 }
 
+impl<K, V> Clone for BlazeMap<K, V>
+where
+    V: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            len: self.len,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Reuses the allocation of `self` instead of allocating a new one
+    /// whenever it is large enough.
+    #[inline]
+    fn clone_from(&mut self, source: &Self) {
+        self.inner.clone_from(&source.inner);
+        self.len = source.len;
+    }
+}
+
 impl<K, V> Default for BlazeMap<K, V>
 where
     K: BlazeMapId,
 {
     #[inline]
-    #[must_use]
     fn default() -> Self {
         Self::new()
     }
 }
 
+impl<K, V> PartialEq for BlazeMap<K, V>
+where
+    V: PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.inner == other.inner
+    }
+}
+
+impl<K, V> Eq for BlazeMap<K, V> where V: Eq {}
+
+/// Compares the maps lexicographically by their `(key, value)` entries
+/// in ascending offset order.
+///
+/// Maps with the same entries are ordered by the lengths of their underlying
+/// storage, so that the ordering is consistent with [`PartialEq`].
+impl<K, V> PartialOrd for BlazeMap<K, V>
+where
+    K: BlazeMapId,
+    V: PartialOrd,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        match self.iter_offsets().partial_cmp(other.iter_offsets())? {
+            core::cmp::Ordering::Equal => Some(self.inner.len().cmp(&other.inner.len())),
+            ordering => Some(ordering),
+        }
+    }
+}
+
+/// Compares the maps lexicographically by their `(key, value)` entries
+/// in ascending offset order.
+///
+/// Maps with the same entries are ordered by the lengths of their underlying
+/// storage, so that the ordering is consistent with [`Eq`].
+impl<K, V> Ord for BlazeMap<K, V>
+where
+    K: BlazeMapId,
+    V: Ord,
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.iter_offsets()
+            .cmp(other.iter_offsets())
+            .then_with(|| self.inner.len().cmp(&other.inner.len()))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> PartialEq<HashMap<K, V, S>> for BlazeMap<K, V>
+where
+    K: BlazeMapId,
+    V: PartialEq,
+    S: BuildHasher,
+{
+    #[inline]
+    fn eq(&self, other: &HashMap<K, V, S>) -> bool {
+        debug_assert_eq!(
+            self.inner.iter().filter_map(Option::as_ref).count(),
+            self.len
+        );
+        self.len == other.len()
+            && other.iter().all(|(key, value)| {
+                self.inner
+                    .get(key.get_offset())
+                    .and_then(Option::as_ref)
+                    .is_some_and(|v| v == value)
+            })
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> PartialEq<BlazeMap<K, V>> for HashMap<K, V, S>
+where
+    K: BlazeMapId,
+    V: PartialEq,
+    S: BuildHasher,
+{
+    #[inline]
+    fn eq(&self, other: &BlazeMap<K, V>) -> bool {
+        other == self
+    }
+}
+
 macro_rules! blaze_map_orig_key_blocking_iter {
     ($self:ident, $iter:ident, $guard:ident) => {
         let $guard = K::static_container().key_by_offset_provider();
@@ -463,7 +1378,7 @@ where
     V: Debug,
 {
     #[inline]
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         blaze_map_orig_key_blocking_iter!(self, iter, guard);
         let mut debug_map = f.debug_map();
         for (key, value) in iter {
@@ -511,34 +1426,40 @@ where
 }
 
 #[cfg(feature = "serde")]
-struct BlazeMapDeserializer<K, V>(PhantomData<(K, V)>);
-
-#[cfg(feature = "serde")]
-impl<'de, K, V> Visitor<'de> for BlazeMapDeserializer<K, V>
+impl<K, V> BlazeMap<K, V>
 where
     K: BlazeMapIdWrapper + BlazeMapIdStatic,
-    <K as BlazeMapId>::OrigType: Deserialize<'de>,
-    V: Deserialize<'de>,
+    V: Default,
 {
-    type Value = BlazeMap<K, V>;
-
-    #[inline]
-    fn expecting(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(formatter, "BlazeMap-compatible map")
-    }
-
+    /// Deserializes a [`BlazeMap`] and then fills every registered key
+    /// lacking a value with `V::default()`,
+    /// so that the resulting map is complete over the `K` id space.
+    ///
+    /// Intended to be used as
+    /// `#[serde(deserialize_with = "BlazeMap::deserialize_with_defaults")]`.
+    ///
+    /// # Errors
+    /// Returns an error if the underlying deserialization fails.
     #[inline]
-    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    pub fn deserialize_with_defaults<'de, D>(deserializer: D) -> Result<Self, D::Error>
     where
-        A: MapAccess<'de>,
+        D: Deserializer<'de>,
+        <K as BlazeMapId>::OrigType: Deserialize<'de>,
+        V: Deserialize<'de>,
     {
-        let mut result = BlazeMap::with_current_key_type_capacity();
-
-        while let Some((key, value)) = map.next_entry::<K::OrigType, V>()? {
-            let key = unsafe { K::new(K::static_container(), key) };
-            result.insert(key, value);
+        let mut result = Self::deserialize(deserializer)?;
+        let current_capacity = K::static_container()
+            .capacity_info_provider()
+            .offset_capacity();
+        if result.inner.len() < current_capacity {
+            result.inner.resize_with(current_capacity, || None);
+        }
+        for value in &mut result.inner {
+            if value.is_none() {
+                *value = Some(V::default());
+                result.len += 1;
+            }
         }
-        result.shrink_to_fit();
         debug_assert_eq!(
             result.inner.iter().filter_map(Option::as_ref).count(),
             result.len
@@ -546,3 +1467,1970 @@ where
         Ok(result)
     }
 }
+
+#[cfg(feature = "serde")]
+impl<K, V> BlazeMap<K, V>
+where
+    K: BlazeMapIdStatic,
+{
+    /// Serializes the map as a sequence of `(key, value)` pairs
+    /// instead of a map.
+    ///
+    /// Useful when the original key type isn't a valid map key
+    /// in the target format, e.g. a struct key in JSON.
+    /// Intended to be used as
+    /// `#[serde(serialize_with = "BlazeMap::serialize_as_seq")]`
+    /// along with [`deserialize_from_seq`](BlazeMap::deserialize_from_seq).
+    ///
+    /// # Errors
+    /// Returns an error if the underlying serialization fails.
+    #[inline]
+    pub fn serialize_as_seq<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        <K as BlazeMapId>::OrigType: Serialize,
+        V: Serialize,
+    {
+        blaze_map_orig_key_blocking_iter!(self, iter, guard);
+        let mut serializer = serializer.serialize_seq(Some(self.len))?;
+        for (key, value) in iter {
+            serializer.serialize_element(&(key.borrow(), value))?;
+        }
+        serializer.end()
+    }
+
+    /// Serializes the map in a compact form: the number of entries
+    /// followed by the raw per-offset values, without the original keys.
+    ///
+    /// This is much more compact than the default representation
+    /// for dense maps, especially with binary formats such as `bincode`.
+    /// Intended to be used as
+    /// `#[serde(serialize_with = "BlazeMap::serialize_compact")]`
+    /// along with [`deserialize_compact`](BlazeMap::deserialize_compact).
+    ///
+    /// Note that the keys are restored from their offsets,
+    /// so the deserializing side must have registered exactly the same keys
+    /// in exactly the same order as the serializing side.
+    ///
+    /// # Errors
+    /// Returns an error if the underlying serialization fails.
+    #[inline]
+    pub fn serialize_compact<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        V: Serialize,
+    {
+        debug_assert_eq!(
+            self.inner.iter().filter_map(Option::as_ref).count(),
+            self.len
+        );
+        (self.len, &self.inner).serialize(serializer)
+    }
+
+    /// Deserializes a [`BlazeMap`] from the compact form produced by
+    /// [`serialize_compact`](BlazeMap::serialize_compact).
+    ///
+    /// Intended to be used as
+    /// `#[serde(deserialize_with = "BlazeMap::deserialize_compact")]`.
+    ///
+    /// The keys are restored from their offsets,
+    /// so the deserializing side must have registered exactly the same keys
+    /// in exactly the same order as the serializing side.
+    /// This precondition can't be verified; only the offsets that aren't
+    /// registered at all are rejected.
+    ///
+    /// # Errors
+    /// Returns an error if the underlying deserialization fails,
+    /// if the number of entries doesn't match the values,
+    /// or if any value has an unregistered offset.
+    #[inline]
+    pub fn deserialize_compact<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+        V: Deserialize<'de>,
+    {
+        let (len, mut inner) = <(usize, Vec<Option<V>>)>::deserialize(deserializer)?;
+        if inner.iter().filter_map(Option::as_ref).count() != len {
+            return Err(serde::de::Error::custom(
+                "BlazeMap length doesn't match the number of values",
+            ));
+        }
+        let offset_capacity = K::static_container()
+            .capacity_info_provider()
+            .offset_capacity();
+        if let Some(offset) = inner
+            .iter()
+            .rposition(Option::is_some)
+            .filter(|offset| *offset >= offset_capacity)
+        {
+            return Err(serde::de::Error::custom(format_args!(
+                "BlazeMap offset {offset} isn't registered"
+            )));
+        }
+        inner.truncate(offset_capacity);
+        Ok(Self {
+            inner,
+            len,
+            phantom: PhantomData,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<K, V> BlazeMap<K, V>
+where
+    K: BlazeMapIdWrapper + BlazeMapIdStatic,
+{
+    /// Deserializes a [`BlazeMap`] from a sequence of `(key, value)` pairs,
+    /// as produced by [`serialize_as_seq`](BlazeMap::serialize_as_seq).
+    ///
+    /// Intended to be used as
+    /// `#[serde(deserialize_with = "BlazeMap::deserialize_from_seq")]`.
+    ///
+    /// # Errors
+    /// Returns an error if the underlying deserialization fails.
+    #[inline]
+    pub fn deserialize_from_seq<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+        <K as BlazeMapId>::OrigType: Deserialize<'de>,
+        V: Deserialize<'de>,
+    {
+        deserializer.deserialize_seq(BlazeMapSeqDeserializer(PhantomData))
+    }
+}
+
+#[cfg(feature = "serde")]
+struct BlazeMapDeserializer<K, V>(PhantomData<(K, V)>);
+
+#[cfg(feature = "serde")]
+impl<'de, K, V> Visitor<'de> for BlazeMapDeserializer<K, V>
+where
+    K: BlazeMapIdWrapper + BlazeMapIdStatic,
+    <K as BlazeMapId>::OrigType: Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    type Value = BlazeMap<K, V>;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(formatter, "BlazeMap-compatible map")
+    }
+
+    #[inline]
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut result = BlazeMap::with_current_key_type_capacity();
+
+        while let Some((key, value)) = map.next_entry::<K::OrigType, V>()? {
+            let key = unsafe { K::new(K::static_container(), key) };
+            result.insert(key, value);
+        }
+        result.shrink_to_fit();
+        debug_assert_eq!(
+            result.inner.iter().filter_map(Option::as_ref).count(),
+            result.len
+        );
+        Ok(result)
+    }
+}
+
+#[cfg(feature = "serde")]
+struct BlazeMapSeqDeserializer<K, V>(PhantomData<(K, V)>);
+
+#[cfg(feature = "serde")]
+impl<'de, K, V> Visitor<'de> for BlazeMapSeqDeserializer<K, V>
+where
+    K: BlazeMapIdWrapper + BlazeMapIdStatic,
+    <K as BlazeMapId>::OrigType: Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    type Value = BlazeMap<K, V>;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(formatter, "sequence of BlazeMap-compatible key-value pairs")
+    }
+
+    #[inline]
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut result = BlazeMap::with_current_key_type_capacity();
+
+        while let Some((key, value)) = seq.next_element::<(K::OrigType, V)>()? {
+            let key = unsafe { K::new(K::static_container(), key) };
+            result.insert(key, value);
+        }
+        result.shrink_to_fit();
+        debug_assert_eq!(
+            result.inner.iter().filter_map(Option::as_ref).count(),
+            result.len
+        );
+        Ok(result)
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl<K, V> BorshSerialize for BlazeMap<K, V>
+where
+    K: BlazeMapIdStatic,
+    <K as BlazeMapId>::OrigType: BorshSerialize,
+    V: BorshSerialize,
+{
+    /// Serializes the map as a `u32`-length-prefixed sequence
+    /// of `(original key, value)` pairs.
+    #[inline]
+    fn serialize<W>(&self, writer: &mut W) -> std::io::Result<()>
+    where
+        W: std::io::Write,
+    {
+        let len = u32::try_from(self.len).map_err(|_| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "BlazeMap length exceeds u32::MAX",
+            )
+        })?;
+        BorshSerialize::serialize(&len, writer)?;
+        blaze_map_orig_key_blocking_iter!(self, iter, guard);
+        for (key, value) in iter {
+            BorshSerialize::serialize(key.borrow(), writer)?;
+            BorshSerialize::serialize(value, writer)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl<K, V> BorshDeserialize for BlazeMap<K, V>
+where
+    K: BlazeMapIdWrapper + BlazeMapIdStatic,
+    <K as BlazeMapId>::OrigType: BorshDeserialize,
+    V: BorshDeserialize,
+{
+    #[inline]
+    fn deserialize_reader<R>(reader: &mut R) -> std::io::Result<Self>
+    where
+        R: std::io::Read,
+    {
+        let len = u32::deserialize_reader(reader)?;
+        let mut result = BlazeMap::with_current_key_type_capacity();
+        for _ in 0..len {
+            let key = K::OrigType::deserialize_reader(reader)?;
+            let value = V::deserialize_reader(reader)?;
+            let key = unsafe { K::new(K::static_container(), key) };
+            result.insert(key, value);
+        }
+        result.shrink_to_fit();
+        debug_assert_eq!(
+            result.inner.iter().filter_map(Option::as_ref).count(),
+            result.len
+        );
+        Ok(result)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, K, V> Arbitrary<'a> for BlazeMap<K, V>
+where
+    K: BlazeMapIdWrapper + BlazeMapIdStatic,
+    <K as BlazeMapId>::OrigType: Arbitrary<'a>,
+    V: Arbitrary<'a>,
+{
+    #[inline]
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        from_arbitrary_pairs(u.arbitrary_iter()?)
+    }
+
+    #[inline]
+    fn arbitrary_take_rest(u: Unstructured<'a>) -> arbitrary::Result<Self> {
+        from_arbitrary_pairs(u.arbitrary_take_rest_iter()?)
+    }
+}
+
+/// Collects the arbitrary `(original key, value)` pairs into a [`BlazeMap`].
+#[cfg(feature = "arbitrary")]
+#[inline]
+fn from_arbitrary_pairs<K, V>(
+    pairs: impl Iterator<Item = arbitrary::Result<(K::OrigType, V)>>,
+) -> arbitrary::Result<BlazeMap<K, V>>
+where
+    K: BlazeMapIdWrapper + BlazeMapIdStatic,
+{
+    let mut result = BlazeMap::with_current_key_type_capacity();
+    for pair in pairs {
+        let (key, value) = pair?;
+        let key = unsafe { K::new(K::static_container(), key) };
+        result.insert(key, value);
+    }
+    result.shrink_to_fit();
+    Ok(result)
+}
+
+#[cfg(all(test, not(feature = "loom")))]
+mod tests {
+    use crate::{
+        collections::{
+            map::{BlazeMap, Entry, Iter, Keys, Values, ValuesMutChunk},
+            set::BlazeSet,
+        },
+        define_key_wrapper, define_key_wrapper_bounded, define_plain_id,
+        error::CapacityOverflow,
+        prelude::{BlazeMapId, BlazeMapIdStatic},
+    };
+    use std::{borrow::Borrow, collections::HashMap};
+
+    #[allow(dead_code)]
+    mod id {
+        super::define_plain_id! {
+            pub(super) struct Id
+        }
+    }
+    use id::Id;
+
+    fn id(offset: usize) -> Id {
+        unsafe { Id::from_offset_unchecked(offset) }
+    }
+
+    #[test]
+    fn get_checked_capacity() {
+        let mut map = BlazeMap::new();
+        for offset in (0..1_000).step_by(3) {
+            map.insert(id(offset), offset);
+        }
+        for offset in 0..2_000 {
+            assert_eq!(map.get_checked_capacity(id(offset)), map.get(id(offset)));
+        }
+    }
+
+    #[test]
+    fn iter_offsets() {
+        let offsets = [1, 4, 5, 9, 20];
+        let mut map = BlazeMap::new();
+        for offset in offsets {
+            map.insert(id(offset), offset * 10);
+        }
+        map.insert(id(30), 300);
+        map.remove(id(30));
+        assert_eq!(
+            map.iter_offsets()
+                .map(|(offset, value)| (offset, *value))
+                .collect::<Vec<_>>(),
+            offsets.map(|offset| (offset, offset * 10))
+        );
+        assert_eq!(
+            map.iter_offsets()
+                .map(|(offset, _)| offset)
+                .collect::<Vec<_>>(),
+            map.keys().map(Id::get_offset).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn default_iters() {
+        let mut iter = Iter::<Id, String>::default();
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next(), None);
+        let mut keys = Keys::<Id, String>::default();
+        assert_eq!(keys.len(), 0);
+        assert_eq!(keys.next(), None);
+        let mut values = Values::<Id, String>::default();
+        assert_eq!(values.len(), 0);
+        assert_eq!(values.next(), None);
+        let mut map = BlazeMap::new();
+        map.insert(id(1), "1".to_string());
+        let mut iter = map.iter();
+        assert_eq!(std::mem::take(&mut iter).count(), 1);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn get_many_unchecked_mut() {
+        let mut map = BlazeMap::new();
+        for offset in [0, 3, 7, 8] {
+            map.insert(id(offset), offset * 10);
+        }
+        let keys = [id(8), id(0), id(7)];
+        let expected = keys.map(|key| *map.get_mut(key).unwrap());
+        let values = unsafe { map.get_many_unchecked_mut(keys) };
+        assert_eq!(values.map(|value| *value), expected);
+        for value in unsafe { map.get_many_unchecked_mut(keys) } {
+            *value += 1;
+        }
+        assert_eq!(map.get(id(0)), Some(&1));
+        assert_eq!(map.get(id(3)), Some(&30));
+        assert_eq!(map.get(id(7)), Some(&71));
+        assert_eq!(map.get(id(8)), Some(&81));
+    }
+
+    #[test]
+    fn get_by_offset() {
+        let mut map = BlazeMap::new();
+        map.insert(id(1), "1");
+        map.insert(id(4), "4");
+        map.insert(id(6), "6");
+        map.remove(id(6));
+
+        assert!(map.contains_offset(1));
+        assert_eq!(map.get_by_offset(1), Some(&"1"));
+        assert!(map.contains_offset(4));
+        assert_eq!(map.get_by_offset(4), Some(&"4"));
+
+        for offset in [0, 2, 5] {
+            assert!(!map.contains_offset(offset));
+            assert_eq!(map.get_by_offset(offset), None);
+        }
+        for offset in [6, 7, 1_000] {
+            assert!(!map.contains_offset(offset));
+            assert_eq!(map.get_by_offset(offset), None);
+        }
+    }
+
+    #[test]
+    fn drain_double_ended() {
+        let offsets = [0, 2, 3, 5, 8, 9];
+        for num_consumed in 0..=offsets.len() {
+            let mut map = BlazeMap::new();
+            for offset in offsets {
+                map.insert(id(offset), offset * 10);
+            }
+            let mut drain = map.drain();
+            let (mut front, mut back) = (0, offsets.len());
+            for step in 0..num_consumed {
+                if step % 2 == 0 {
+                    assert_eq!(
+                        drain.next(),
+                        Some((id(offsets[front]), offsets[front] * 10))
+                    );
+                    front += 1;
+                } else {
+                    back -= 1;
+                    assert_eq!(
+                        drain.next_back(),
+                        Some((id(offsets[back]), offsets[back] * 10))
+                    );
+                }
+                assert_eq!(drain.len(), back - front);
+            }
+            if num_consumed == offsets.len() {
+                assert_eq!(drain.next(), None);
+                assert_eq!(drain.next_back(), None);
+            }
+            drop(drain);
+            assert!(map.is_empty());
+            assert_eq!(map.iter().next(), None);
+        }
+    }
+
+    #[test]
+    fn iter_mut_split_at_offset() {
+        let mut map = BlazeMap::new();
+        for offset in (0..100).step_by(3) {
+            map.insert(id(offset), offset);
+        }
+        let mut iter = map.iter_mut();
+        assert_eq!(iter.next().map(|(key, _)| key), Some(id(0)));
+        let (head, tail) = iter.split_at_offset(50);
+        assert_eq!(head.len(), 16);
+        assert_eq!(tail.len(), 17);
+        std::thread::scope(|scope| {
+            scope.spawn(move || head.for_each(|(_, value)| *value += 1_000));
+            scope.spawn(move || tail.for_each(|(_, value)| *value += 2_000));
+        });
+        for (key, value) in &map {
+            let offset = key.get_offset();
+            let expected = match offset {
+                0 => 0,
+                1..50 => offset + 1_000,
+                _ => offset + 2_000,
+            };
+            assert_eq!(*value, expected);
+        }
+
+        let (head, tail) = map.iter_mut().split_at_offset(1_000);
+        assert_eq!(head.len(), 34);
+        assert_eq!(tail.count(), 0);
+        let (head, tail) = map.iter_mut().split_at_offset(0);
+        assert_eq!(head.count(), 0);
+        assert_eq!(tail.len(), 34);
+    }
+
+    #[test]
+    fn shrink_to() {
+        let mut map = BlazeMap::new();
+        map.inner.reserve_exact(1_000);
+        for offset in [1, 5, 9] {
+            map.insert(id(offset), offset);
+        }
+        map.insert(id(500), 500);
+        map.remove(id(500));
+
+        map.shrink_to(100);
+        assert!(map.inner.capacity() >= 100);
+        assert!(map.inner.capacity() < 1_000);
+        assert_eq!(map.len(), 3);
+        assert_eq!(
+            map.iter()
+                .map(|(key, value)| (key, *value))
+                .collect::<Vec<_>>(),
+            [(id(1), 1), (id(5), 5), (id(9), 9)]
+        );
+
+        map.shrink_to(0);
+        assert!(map.inner.capacity() >= 10);
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.get(id(9)), Some(&9));
+
+        let capacity = map.inner.capacity();
+        map.shrink_to(2_000);
+        assert_eq!(map.inner.capacity(), capacity);
+    }
+
+    #[test]
+    fn entry_and_replace_entry_with() {
+        let mut map = BlazeMap::new();
+        map.insert(id(1), 10);
+        map.insert(id(3), 30);
+
+        let entry = map
+            .entry(id(1))
+            .and_replace_entry_with(|key, value| Some(value + key.get_offset()));
+        assert!(matches!(entry, Entry::Occupied(_)));
+        assert_eq!(map.get(id(1)), Some(&11));
+        assert_eq!(map.len(), 2);
+
+        let entry = map.entry(id(3)).and_replace_entry_with(|_, _| None);
+        let Entry::Vacant(entry) = entry else {
+            panic!("entry must be vacant");
+        };
+        assert_eq!(entry.insert(31), &31);
+        assert_eq!(map.len(), 2);
+        let entry = map.entry(id(3)).and_replace_entry_with(|_, _| None);
+        assert!(matches!(entry, Entry::Vacant(_)));
+        assert_eq!(map.get(id(3)), None);
+        assert_eq!(map.len(), 1);
+
+        let entry = map
+            .entry(id(5))
+            .and_replace_entry_with(|_, _| unreachable!("entry is vacant"));
+        assert!(matches!(entry, Entry::Vacant(_)));
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.get(id(5)), None);
+        assert_eq!(
+            map.iter()
+                .map(|(key, value)| (key, *value))
+                .collect::<Vec<_>>(),
+            [(id(1), 11)]
+        );
+    }
+
+    #[test]
+    fn into_iter_clone() {
+        let mut map = BlazeMap::new();
+        for offset in [0, 2, 3, 5, 8, 9] {
+            map.insert(id(offset), offset.to_string());
+        }
+
+        let mut iter = map.clone().into_iter();
+        iter.next();
+        iter.next();
+        let cloned = iter.clone();
+        assert_eq!(cloned.len(), 4);
+        assert_eq!(cloned.collect::<Vec<_>>(), iter.collect::<Vec<_>>());
+
+        let mut keys = map.clone().into_keys();
+        keys.next();
+        assert_eq!(keys.clone().collect::<Vec<_>>(), keys.collect::<Vec<_>>());
+
+        let mut values = map.into_values();
+        values.next();
+        assert_eq!(
+            values.clone().collect::<Vec<_>>(),
+            values.collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn entry_or_try_insert_with() {
+        let mut map = BlazeMap::new();
+        map.insert(id(1), 10);
+
+        let value = map
+            .entry(id(1))
+            .or_try_insert_with(|| -> Result<_, ()> { unreachable!("entry is occupied") });
+        assert_eq!(value, Ok(&mut 10));
+
+        let value = map.entry(id(3)).or_try_insert_with(|| Ok::<_, ()>(30));
+        assert_eq!(value, Ok(&mut 30));
+        assert_eq!(map.len(), 2);
+
+        let value = map.entry(id(7)).or_try_insert_with(|| Err("failed"));
+        assert_eq!(value, Err("failed"));
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(id(7)), None);
+        assert_eq!(
+            map.iter()
+                .map(|(key, value)| (key, *value))
+                .collect::<Vec<_>>(),
+            [(id(1), 10), (id(3), 30)]
+        );
+    }
+
+    #[test]
+    fn map_values() {
+        let mut map = BlazeMap::<Id, u32>::new();
+        for offset in [0, 2, 5, 9] {
+            map.insert(id(offset), u32::try_from(offset).unwrap() * 10);
+        }
+        map.remove(id(9));
+
+        let strings: BlazeMap<Id, String> = map.map_values(u32::to_string);
+        assert_eq!(strings.len(), map.len());
+        for offset in 0..10 {
+            assert_eq!(
+                strings.get(id(offset)),
+                map.get(id(offset)).map(u32::to_string).as_ref()
+            );
+        }
+
+        let halves = map.clone().into_map_values(|value| value / 2);
+        assert_eq!(halves.len(), 3);
+        assert_eq!(
+            halves
+                .iter()
+                .map(|(key, value)| (key, *value))
+                .collect::<Vec<_>>(),
+            [(id(0), 0), (id(2), 10), (id(5), 25)]
+        );
+    }
+
+    #[test]
+    #[allow(dead_code)]
+    fn insert_many_dense() {
+        define_plain_id! {
+            struct DenseId
+        }
+
+        let ids: Vec<_> = DenseId::new_n(1_000).collect();
+        let mut map = BlazeMap::new();
+        map.insert(ids[10], 0);
+        map.insert(ids[999], 0);
+        map.insert_many_dense(5, 5..1_000);
+        assert_eq!(map.len(), 995);
+        assert_eq!(map.get(ids[4]), None);
+        assert_eq!(map.get(ids[5]), Some(&5));
+        assert_eq!(map.get(ids[10]), Some(&10));
+        assert_eq!(map.get(ids[500]), Some(&500));
+        assert_eq!(map.get(ids[999]), Some(&999));
+
+        map.insert_many_dense(0, [100, 101]);
+        assert_eq!(map.len(), 997);
+        assert_eq!(map.get(ids[1]), Some(&101));
+
+        let result = std::panic::catch_unwind(move || {
+            let mut map = BlazeMap::<DenseId, _>::new();
+            map.insert_many_dense(999, [1, 2]);
+        });
+        assert!(result.is_err());
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            map.insert_many_dense(990, [1; 20]);
+        }));
+        assert!(result.is_err());
+        assert_eq!(map.len(), 997);
+        assert_eq!(map.get(ids[990]), Some(&990));
+        assert_eq!(map.get(ids[999]), Some(&999));
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            map.insert_many_dense(998, (0..3).filter(|_| true));
+        }));
+        assert!(result.is_err());
+        assert_eq!(map.get(ids[998]), Some(&0));
+        assert_eq!(map.get(ids[999]), Some(&1));
+    }
+
+    #[test]
+    fn owning_iters_send_sync() {
+        use crate::collections::map::{Drain, IntoIter, IntoKeys, IntoValues};
+        use static_assertions::{assert_impl_all, assert_not_impl_any};
+        use std::{cell::Cell, rc::Rc};
+
+        assert_impl_all!(IntoIter<Id, String>: Send, Sync);
+        assert_impl_all!(IntoKeys<Id, String>: Send, Sync);
+        assert_impl_all!(IntoValues<Id, String>: Send, Sync);
+        assert_impl_all!(Drain<'static, Id, String>: Send, Sync);
+        assert_not_impl_any!(IntoIter<Id, Rc<u8>>: Send, Sync);
+        assert_not_impl_any!(IntoValues<Id, Cell<u8>>: Sync);
+        assert_not_impl_any!(Drain<'static, Id, Cell<u8>>: Sync);
+
+        let mut map = BlazeMap::new();
+        for offset in [1, 4, 6] {
+            map.insert(id(offset), offset.to_string());
+        }
+        let iter = map.clone().into_iter();
+        let collected = std::thread::spawn(move || iter.collect::<Vec<_>>())
+            .join()
+            .unwrap();
+        assert_eq!(collected.len(), 3);
+        let drained = std::thread::scope(|scope| {
+            let drain = map.drain();
+            scope.spawn(move || drain.count()).join().unwrap()
+        });
+        assert_eq!(drained, 3);
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn drain_range() {
+        let mut map = BlazeMap::new();
+        for offset in [0, 2, 3, 5, 8, 9] {
+            map.insert(id(offset), offset * 10);
+        }
+
+        let drained: Vec<_> = map.drain_range(1..5).collect();
+        assert_eq!(drained, [(id(2), 20), (id(3), 30)]);
+        assert_eq!(map.len(), 4);
+        assert_eq!(map.keys().collect::<Vec<_>>(), [id(0), id(5), id(8), id(9)]);
+
+        let drained: Vec<_> = map.drain_range(8..100).collect();
+        assert_eq!(drained, [(id(8), 80), (id(9), 90)]);
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.keys().collect::<Vec<_>>(), [id(0), id(5)]);
+        assert_eq!(map.inner.len(), 6);
+        map.insert(id(9), 91);
+        assert_eq!(map.get(id(9)), Some(&91));
+
+        assert_eq!(map.drain_range(3..3).count(), 0);
+        assert_eq!(map.drain_range(100..200).count(), 0);
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = map.drain_range(7..2).count();
+        assert_eq!(reversed, 0);
+        assert_eq!(map.len(), 3);
+
+        let mut drain = map.drain_range(0..6);
+        assert_eq!(drain.next(), Some((id(0), 0)));
+        drop(drain);
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.keys().collect::<Vec<_>>(), [id(9)]);
+        assert_eq!(map.inner.len(), 10);
+    }
+
+    #[test]
+    fn drain_range_dropped_unconsumed() {
+        let mut map = BlazeMap::new();
+        for offset in [0, 2, 3, 5, 8, 9] {
+            map.insert(id(offset), offset * 10);
+        }
+
+        drop(map.drain_range(1..4));
+        assert_eq!(map.len(), 4);
+        assert_eq!(map.keys().collect::<Vec<_>>(), [id(0), id(5), id(8), id(9)]);
+        assert_eq!(map.inner.len(), 10);
+
+        drop(map.drain_range(4..100));
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.keys().collect::<Vec<_>>(), [id(0)]);
+        assert_eq!(map.inner.len(), 1);
+        map.insert(id(9), 90);
+        assert_eq!(map.get(id(9)), Some(&90));
+    }
+
+    #[test]
+    fn iters_debug() {
+        define_key_wrapper! {
+            struct Key(&'static str);
+            Derive(as for Original Type): {
+                Debug
+            }
+        }
+
+        let mut map = BlazeMap::new();
+        for (key, value) in [("first", 1), ("second", 2), ("third", 3)] {
+            map.insert(Key::new(key), value);
+        }
+        map.remove(Key::new("second"));
+
+        let expected = r#"{"first": 1, "third": 3}"#;
+        assert_eq!(format!("{map:?}"), expected);
+        assert_eq!(format!("{:?}", map.iter()), expected);
+        assert_eq!(format!("{:?}", map.iter_mut()), expected);
+        assert_eq!(format!("{:?}", map.keys()), r#"["first", "third"]"#);
+        assert_eq!(format!("{:?}", map.values()), "[1, 3]");
+        let mut iter = map.iter();
+        iter.next();
+        assert_eq!(format!("{iter:?}"), r#"{"third": 3}"#);
+        assert_eq!(format!("{:?}", map.clone().into_iter()), expected);
+        assert_eq!(format!("{:?}", map.drain()), expected);
+    }
+
+    #[test]
+    fn drain_range_debug() {
+        define_key_wrapper! {
+            struct Key(&'static str);
+            Derive(as for Original Type): {
+                Debug
+            }
+        }
+
+        let mut map = BlazeMap::new();
+        for (key, value) in [("first", 1), ("second", 2), ("third", 3), ("fourth", 4)] {
+            map.insert(Key::new(key), value);
+        }
+        map.remove(Key::new("third"));
+
+        let mut drain = map.drain_range(1..3);
+        assert_eq!(format!("{drain:?}"), r#"{"second": 2}"#);
+        drain.next();
+        assert_eq!(format!("{drain:?}"), "{}");
+        drop(drain);
+        assert_eq!(
+            format!("{:?}", map.drain_range(0..10)),
+            r#"{"first": 1, "fourth": 4}"#
+        );
+    }
+
+    #[test]
+    fn extract_if_debug() {
+        define_key_wrapper! {
+            struct Key(&'static str);
+            Derive(as for Original Type): {
+                Debug
+            }
+        }
+
+        let mut map = BlazeMap::new();
+        for (key, value) in [("first", 1), ("second", 2), ("third", 3), ("fourth", 4)] {
+            map.insert(Key::new(key), value);
+        }
+
+        let mut extract = map.extract_if(|_, value| *value % 2 == 0);
+        assert_eq!(
+            format!("{extract:?}"),
+            r#"{"first": 1, "second": 2, "third": 3, "fourth": 4}"#
+        );
+        assert_eq!(extract.next().map(|(_, value)| value), Some(2));
+        assert_eq!(format!("{extract:?}"), r#"{"third": 3, "fourth": 4}"#);
+        assert_eq!(extract.next().map(|(_, value)| value), Some(4));
+        assert_eq!(format!("{extract:?}"), "{}");
+    }
+
+    #[test]
+    fn occupied_entry_insert_get() {
+        let mut map = BlazeMap::new();
+        map.insert(id(2), 20);
+        let Entry::Occupied(mut entry) = map.entry(id(2)) else {
+            panic!("entry must be occupied");
+        };
+        let (old, new) = entry.insert_get(21);
+        assert_eq!(old, 20);
+        assert_eq!(*new, 21);
+        *new += 1;
+        assert_eq!(entry.get(), &22);
+        assert_eq!(map.get(id(2)), Some(&22));
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn key_set() {
+        let mut map = BlazeMap::new();
+        assert!(map.key_set().is_empty());
+        for offset in [1, 8, 9, 30] {
+            map.insert(id(offset), offset);
+        }
+        map.insert(id(40), 40);
+        map.remove(id(40));
+
+        let set = map.key_set();
+        assert_eq!(set.len(), map.len());
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            map.keys().collect::<Vec<_>>()
+        );
+
+        let rebuilt = set.into_map_with(BlazeMapId::get_offset);
+        assert_eq!(rebuilt.len(), map.len());
+        assert_eq!(
+            rebuilt.iter().collect::<Vec<_>>(),
+            map.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn iter_with_keys() {
+        define_key_wrapper! {
+            struct Key(String)
+        }
+
+        let mut map = BlazeMap::new();
+        assert_eq!(map.iter_with_keys().count(), 0);
+        let third = Key::new("third");
+        map.insert(Key::new("first"), 1);
+        map.insert(third, 3);
+        map.insert(Key::new("second"), 2);
+        map.remove(third);
+
+        let entries: Vec<(String, i32)> = map
+            .iter_with_keys()
+            .map(|(key, value)| (key.borrow().clone(), *value))
+            .collect();
+        assert_eq!(
+            entries,
+            [("first".to_string(), 1), ("second".to_string(), 2)]
+        );
+    }
+
+    #[test]
+    fn ord() {
+        use std::collections::BTreeSet;
+
+        let map = |entries: &[(usize, i32)]| {
+            let mut map = BlazeMap::new();
+            for (offset, value) in entries {
+                map.insert(id(*offset), *value);
+            }
+            map
+        };
+        let empty = map(&[]);
+        let small = map(&[(0, 1)]);
+        let large = map(&[(0, 2)]);
+        let longer = map(&[(0, 1), (5, 0)]);
+        let later_key = map(&[(1, 0)]);
+
+        assert!(empty < small);
+        assert!(small < large);
+        assert!(small < longer);
+        assert!(large < later_key);
+        assert!(longer < later_key);
+        assert_eq!(small.cmp(&small.clone()), core::cmp::Ordering::Equal);
+
+        let mut padded = small.clone();
+        padded.inner.push(None);
+        assert_ne!(small, padded);
+        assert!(small < padded);
+        assert!(padded < large);
+
+        let set: BTreeSet<_> = [
+            later_key.clone(),
+            large.clone(),
+            empty.clone(),
+            small.clone(),
+        ]
+        .into_iter()
+        .chain([small.clone(), longer.clone()])
+        .collect();
+        assert_eq!(
+            set.into_iter().collect::<Vec<_>>(),
+            [empty, small, longer, large, later_key]
+        );
+    }
+
+    #[test]
+    fn get_mut_or_key() {
+        let mut map = BlazeMap::new();
+        map.insert(id(1), 10);
+
+        *map.get_mut_or_key(id(1)).unwrap() += 1;
+        assert_eq!(map.get(id(1)), Some(&11));
+
+        let snapshot = map.clone();
+        assert_eq!(map.get_mut_or_key(id(0)).unwrap_err(), id(0));
+        assert_eq!(map.get_mut_or_key(id(5)).unwrap_err(), id(5));
+        assert_eq!(map, snapshot);
+        assert_eq!(map.inner.len(), 2);
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn iter_mut_with_keys() {
+        define_key_wrapper! {
+            struct Key(String)
+        }
+
+        let mut map = BlazeMap::new();
+        let first = Key::new("a");
+        let second = Key::new("abc");
+        let third = Key::new("ab");
+        map.insert(first, 1);
+        map.insert(second, 2);
+        map.insert(third, 3);
+        map.remove(third);
+
+        for (key, value) in map.iter_mut_with_keys() {
+            *value *= key.borrow().len();
+        }
+        assert_eq!(map.get(first), Some(&1));
+        assert_eq!(map.get(second), Some(&6));
+        assert_eq!(map.get(third), None);
+        assert_eq!(map.len(), 2);
+    }
+
+    #[test]
+    fn first_last_key_value() {
+        let mut map = BlazeMap::new();
+        assert_eq!(map.first_key_value(), None);
+        assert_eq!(map.last_key_value(), None);
+
+        for offset in [2, 5, 9] {
+            map.insert(id(offset), offset);
+        }
+        map.insert(id(12), 12);
+        map.remove(id(12));
+        assert_eq!(map.first_key_value(), Some((id(2), &2)));
+        assert_eq!(map.last_key_value(), Some((id(9), &9)));
+
+        map.remove(id(2));
+        map.remove(id(9));
+        assert_eq!(map.first_key_value(), Some((id(5), &5)));
+        assert_eq!(map.last_key_value(), Some((id(5), &5)));
+
+        map.remove(id(5));
+        assert_eq!(map.first_key_value(), None);
+        assert_eq!(map.last_key_value(), None);
+    }
+
+    #[test]
+    fn pop_first_last() {
+        let mut map = BlazeMap::new();
+        assert_eq!(map.pop_first(), None);
+        assert_eq!(map.pop_last(), None);
+
+        let offsets = [0, 3, 4, 8, 15, 16, 23, 42];
+        for offset in offsets {
+            map.insert(id(offset), offset);
+        }
+
+        let mut front = Vec::new();
+        let mut back = Vec::new();
+        while !map.is_empty() {
+            let (key, value) = map.pop_first().unwrap();
+            assert_eq!(key.get_offset(), value);
+            front.push(value);
+            if let Some((key, value)) = map.pop_last() {
+                assert_eq!(key.get_offset(), value);
+                assert!(map.inner.last().is_none_or(Option::is_some));
+                back.push(value);
+            }
+        }
+        assert_eq!(front, [0, 3, 4, 8]);
+        assert_eq!(back, [42, 23, 16, 15]);
+        assert_eq!(map.len(), 0);
+        assert_eq!(map.pop_first(), None);
+        assert_eq!(map.pop_last(), None);
+
+        map.insert(id(5), 5);
+        assert_eq!(map.pop_last(), Some((id(5), 5)));
+        assert!(map.inner.is_empty());
+    }
+
+    #[test]
+    fn entry_debug() {
+        let mut map = BlazeMap::new();
+        map.insert(id(1), "one");
+
+        let entry = map.entry(id(1));
+        assert_eq!(
+            format!("{entry:?}"),
+            r#"Entry(OccupiedEntry { offset: 1, value: "one" })"#
+        );
+        let Entry::Occupied(entry) = entry else {
+            unreachable!()
+        };
+        assert_eq!(
+            format!("{entry:?}"),
+            r#"OccupiedEntry { offset: 1, value: "one" }"#
+        );
+
+        for offset in [0, 7] {
+            let entry = map.entry(id(offset));
+            assert_eq!(
+                format!("{entry:?}"),
+                format!("Entry(VacantEntry {{ offset: {offset} }})")
+            );
+        }
+    }
+
+    #[test]
+    fn fold_values() {
+        let mut map = BlazeMap::new();
+        assert_eq!(map.fold_values(0, |acc, value| acc + value), 0);
+        for offset in [1, 4, 9, 16, 25] {
+            map.insert(id(offset), offset);
+        }
+        map.remove(id(9));
+        assert_eq!(
+            map.fold_values(0, |acc, value| acc + value),
+            map.values().copied().sum::<usize>()
+        );
+        assert_eq!(
+            map.fold_values(Vec::new(), |mut acc, value| {
+                acc.push(*value);
+                acc
+            }),
+            [1, 4, 16, 25]
+        );
+    }
+
+    #[test]
+    fn retain_in_set() {
+        let mut map = BlazeMap::new();
+        for offset in 0..40 {
+            map.insert(id(offset), offset);
+        }
+        map.remove(id(12));
+
+        let mut allowed = BlazeSet::new();
+        for offset in [0, 3, 12, 17, 20] {
+            allowed.insert(id(offset));
+        }
+        allowed.insert(id(100));
+
+        let mut kept = map.clone();
+        kept.retain_in_set(&allowed);
+        assert_eq!(kept.len(), 4);
+        assert_eq!(
+            kept.iter()
+                .map(|(key, value)| (key.get_offset(), *value))
+                .collect::<Vec<_>>(),
+            [(0, 0), (3, 3), (17, 17), (20, 20)]
+        );
+        assert_eq!(kept.inner.len(), 40);
+
+        let mut short_allowed = BlazeSet::new();
+        short_allowed.extend([0, 3, 12, 17, 20].map(id));
+        let mut pruned = map.clone();
+        pruned.retain_in_set(&short_allowed);
+        assert_eq!(
+            pruned.iter().collect::<Vec<_>>(),
+            kept.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(pruned.inner.len(), 24);
+
+        map.retain_in_set(&BlazeSet::new());
+        assert!(map.is_empty());
+        assert!(map.inner.is_empty());
+    }
+
+    #[test]
+    fn into_iter_into_map() {
+        let mut map = BlazeMap::new();
+        for offset in [0, 2, 3, 5, 8, 9] {
+            map.insert(id(offset), offset * 10);
+        }
+        let capacity = map.inner.capacity();
+
+        let mut iter = map.into_iter();
+        assert_eq!(iter.next(), Some((id(9), 90)));
+        assert_eq!(iter.next(), Some((id(8), 80)));
+        assert_eq!(iter.next(), Some((id(5), 50)));
+
+        let mut map = iter.into_map();
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.inner.capacity(), capacity);
+        assert_eq!(
+            map.iter()
+                .map(|(key, value)| (key, *value))
+                .collect::<Vec<_>>(),
+            [(id(0), 0), (id(2), 20), (id(3), 30)]
+        );
+
+        map.insert(id(9), 90);
+        assert_eq!(map.len(), 4);
+        assert_eq!(map.into_iter().into_map().len(), 4);
+    }
+
+    #[test]
+    fn entry_or_insert_tracked() {
+        let mut map = BlazeMap::new();
+        let mut dirty = false;
+        {
+            let value = map.entry(id(1)).or_insert_tracked(|| 10, &mut dirty);
+            assert_eq!(*value, 10);
+        }
+        assert!(!dirty);
+        {
+            let value = map.entry(id(1)).or_insert_tracked(|| 20, &mut dirty);
+            assert_eq!(*value, 10);
+        }
+        assert!(!dirty);
+        {
+            let mut value = map.entry(id(1)).or_insert_tracked(|| 20, &mut dirty);
+            *value += 1;
+        }
+        assert!(dirty);
+        assert_eq!(map.get(id(1)), Some(&11));
+    }
+
+    #[test]
+    fn try_entry_by_orig_key() {
+        define_key_wrapper_bounded! {
+            struct Key(&'static str);
+            MAX_CAP = 2
+        }
+
+        let mut map = BlazeMap::new();
+        *map.try_entry_by_orig_key("first").unwrap().or_default() += 1;
+        *map.try_entry_by_orig_key("second").unwrap().or_default() += 2;
+        *map.try_entry_by_orig_key("first").unwrap().or_default() += 3;
+        assert!(matches!(
+            map.try_entry_by_orig_key("third"),
+            Err(CapacityOverflow { capacity: 2 })
+        ));
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(Key::new("first")), Some(&4));
+        assert_eq!(map.get(Key::new("second")), Some(&2));
+        *map.entry_by_orig_key("second").or_default() += 1;
+        assert_eq!(map.get(Key::new("second")), Some(&3));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn deserialize_with_defaults() {
+        define_key_wrapper! {
+            struct Key(String);
+            Derive(as for Original Type): {
+                Debug,
+                Deserialize
+            }
+        }
+
+        #[derive(serde::Deserialize)]
+        struct Config {
+            #[serde(deserialize_with = "BlazeMap::deserialize_with_defaults")]
+            limits: BlazeMap<Key, u32>,
+        }
+
+        let a = Key::new("a".to_string());
+        let b = Key::new("b".to_string());
+        let c = Key::new("c".to_string());
+        let config: Config = serde_json::from_str(r#"{"limits": {"b": 5, "d": 7}}"#).unwrap();
+        let limits = config.limits;
+        let d = Key::new("d".to_string());
+        assert_eq!(limits.len(), 4);
+        assert_eq!(limits.get(a), Some(&0));
+        assert_eq!(limits.get(b), Some(&5));
+        assert_eq!(limits.get(c), Some(&0));
+        assert_eq!(limits.get(d), Some(&7));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serialize_as_seq() {
+        #[derive(
+            Clone,
+            Debug,
+            PartialEq,
+            Eq,
+            Hash,
+            serde::Serialize,
+            serde::Deserialize
+        )]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+
+        define_key_wrapper! {
+            struct Key(Point);
+            Derive(as for Original Type): {
+                Debug
+            }
+        }
+
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Plain {
+            map: BlazeMap<Key, u32>,
+        }
+
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct AsSeq {
+            #[serde(
+                serialize_with = "BlazeMap::serialize_as_seq",
+                deserialize_with = "BlazeMap::deserialize_from_seq"
+            )]
+            map: BlazeMap<Key, u32>,
+        }
+
+        let mut map = BlazeMap::new();
+        map.insert(Key::new(Point { x: 1, y: 2 }), 10);
+        map.insert(Key::new(Point { x: -3, y: 4 }), 20);
+
+        assert!(serde_json::to_string(&Plain { map: map.clone() }).is_err());
+
+        let json = serde_json::to_string(&AsSeq { map: map.clone() }).unwrap();
+        assert_eq!(json, r#"{"map":[[{"x":1,"y":2},10],[{"x":-3,"y":4},20]]}"#);
+        let deserialized: AsSeq = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.map, map);
+    }
+
+    #[test]
+    #[cfg(all(feature = "proptest", feature = "serde"))]
+    #[allow(dead_code)]
+    fn proptest_serde_round_trip() {
+        use crate::proptest::blaze_map_strategy;
+        use proptest::{prelude::any, prop_assert_eq, proptest};
+
+        define_key_wrapper! {
+            struct Key(String);
+            Derive(as for Original Type): {
+                Debug
+            }
+        }
+
+        proptest!(|(map in blaze_map_strategy::<Key, _>("[a-z]{1,3}", any::<u32>(), 0..20))| {
+            let json = serde_json::to_string(&map).unwrap();
+            let deserialized: BlazeMap<Key, u32> = serde_json::from_str(&json).unwrap();
+            prop_assert_eq!(deserialized, map);
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "arbitrary")]
+    #[allow(dead_code)]
+    fn arbitrary() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        define_key_wrapper! {
+            struct Key(u8);
+            Derive(as for Original Type): {
+                Debug,
+                Arbitrary
+            }
+        }
+        define_plain_id! {
+            struct PlainId;
+            Derive: {
+                Arbitrary
+            }
+        }
+
+        let bytes: Vec<u8> = (0..=255).rev().collect();
+        let mut u = Unstructured::new(&bytes);
+        let map = BlazeMap::<Key, u16>::arbitrary(&mut u).unwrap();
+        assert!(!map.is_empty());
+        assert_eq!(map.len(), Key::num_registered());
+        assert_eq!(map.iter().count(), map.len());
+
+        let key = Key::arbitrary(&mut u).unwrap();
+        assert!(key.get_offset() < Key::num_registered());
+
+        assert!(PlainId::arbitrary(&mut u).is_err());
+        let ids: Vec<_> = PlainId::new_n(3).collect();
+        assert!(ids.contains(&PlainId::arbitrary(&mut u).unwrap()));
+
+        let map = BlazeMap::<Key, u16>::arbitrary_take_rest(Unstructured::new(&bytes)).unwrap();
+        assert_eq!(map.iter().count(), map.len());
+    }
+
+    #[test]
+    #[cfg(feature = "borsh")]
+    #[allow(dead_code)]
+    fn borsh_round_trip() {
+        define_plain_id! {
+            struct PlainId;
+            Derive: {
+                BorshSerialize
+            }
+        }
+        define_key_wrapper! {
+            struct Key(String);
+            Derive(as for Original Type): {
+                Debug,
+                BorshSerialize,
+                BorshDeserialize
+            }
+        }
+
+        let first = PlainId::new();
+        let second = PlainId::new();
+        let mut map = BlazeMap::new();
+        map.insert(second, 20_u32);
+        map.insert(first, 10);
+        let bytes = borsh::to_vec(&map).unwrap();
+        let pairs: Vec<(usize, u32)> = borsh::from_slice(&bytes).unwrap();
+        assert_eq!(pairs, [(0, 10), (1, 20)]);
+        assert_eq!(
+            borsh::to_vec(&second).unwrap(),
+            borsh::to_vec(&1_usize).unwrap()
+        );
+
+        let mut map = BlazeMap::new();
+        map.insert(Key::new("first"), 1_u64);
+        map.insert(Key::new("second"), 2);
+        map.insert(Key::new("third"), 3);
+        map.remove(Key::new("second"));
+        let bytes = borsh::to_vec(&map).unwrap();
+        let deserialized: BlazeMap<Key, u64> = borsh::from_slice(&bytes).unwrap();
+        assert_eq!(deserialized, map);
+        let pairs: Vec<(String, u64)> = borsh::from_slice(&bytes).unwrap();
+        assert_eq!(pairs, [("first".to_string(), 1), ("third".to_string(), 3)]);
+
+        let key = Key::new("fourth");
+        let bytes = borsh::to_vec(&key).unwrap();
+        assert_eq!(bytes, borsh::to_vec("fourth").unwrap());
+        assert_eq!(borsh::from_slice::<Key>(&bytes).unwrap(), key);
+    }
+
+    #[test]
+    #[cfg(feature = "rkyv")]
+    fn rkyv_round_trip() {
+        use crate::collections::map::ArchivedBlazeMap;
+        use rkyv::rancor::Error;
+
+        define_key_wrapper! {
+            struct Key(String);
+            Derive(as for Original Type): {
+                Debug
+            }
+        }
+
+        let first = Key::new("first");
+        let second = Key::new("second");
+        let third = Key::new("third");
+        let mut map = BlazeMap::new();
+        map.insert(first, 1_u64);
+        map.insert(third, 3);
+
+        let bytes = rkyv::to_bytes::<Error>(&map).unwrap();
+        let archived = rkyv::access::<ArchivedBlazeMap<Key, u64>, Error>(&bytes).unwrap();
+        assert_eq!(archived.len(), 2);
+        assert_eq!(archived.get(first).map(|value| value.to_native()), Some(1));
+        assert_eq!(archived.get(second), None);
+        assert_eq!(archived.get(third).map(|value| value.to_native()), Some(3));
+        assert_eq!(
+            archived
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.to_native()))
+                .collect::<Vec<_>>(),
+            [("first", 1), ("third", 3)]
+        );
+
+        let deserialized: BlazeMap<Key, u64> = rkyv::deserialize::<_, Error>(archived).unwrap();
+        assert_eq!(deserialized, map);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serialize_compact() {
+        define_key_wrapper! {
+            struct Key(String)
+        }
+
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Compact {
+            #[serde(
+                serialize_with = "BlazeMap::serialize_compact",
+                deserialize_with = "BlazeMap::deserialize_compact"
+            )]
+            map: BlazeMap<Key, u32>,
+        }
+
+        let mut map = BlazeMap::new();
+        map.insert(Key::new("first"), 1);
+        map.insert(Key::new("third"), 3);
+        map.insert(Key::new("second"), 2);
+        map.remove(Key::new("third"));
+
+        let json = serde_json::to_string(&Compact { map: map.clone() }).unwrap();
+        assert_eq!(json, r#"{"map":[2,[1,null,2]]}"#);
+        let deserialized: Compact = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.map, map);
+
+        assert!(serde_json::from_str::<Compact>(r#"{"map":[1,[1,null,2]]}"#).is_err());
+        assert!(serde_json::from_str::<Compact>(r#"{"map":[1,[null,null,null,4]]}"#).is_err());
+        let deserialized: Compact =
+            serde_json::from_str(r#"{"map":[1,[null,null,3,null,null]]}"#).unwrap();
+        assert_eq!(deserialized.map.len(), 1);
+        assert_eq!(deserialized.map.get(Key::new("second")), Some(&3));
+    }
+
+    #[test]
+    fn entry_at_offset() {
+        let mut expected = BlazeMap::new();
+        let mut map = BlazeMap::new();
+        for offset in [3, 1, 4, 1, 5, 9, 2, 6, 5, 3] {
+            *expected.entry(id(offset)).or_insert(0) += offset;
+            *unsafe { map.entry_at_offset(offset) }.or_insert(0) += offset;
+        }
+        for offset in [1, 2, 3] {
+            if let Entry::Occupied(entry) = expected.entry(id(offset)) {
+                entry.remove();
+            }
+            if let Entry::Occupied(entry) = unsafe { map.entry_at_offset(offset) } {
+                entry.remove();
+            }
+        }
+        assert_eq!(map.len(), expected.len());
+        assert_eq!(map, expected);
+        assert!(matches!(
+            unsafe { map.entry_at_offset(4) },
+            Entry::Occupied(_)
+        ));
+        assert!(matches!(
+            unsafe { map.entry_at_offset(100) },
+            Entry::Vacant(_)
+        ));
+    }
+
+    #[test]
+    fn entry_or_insert_with_key() {
+        let mut map = BlazeMap::new();
+        let mut calls = 0;
+        for offset in [2, 7, 2] {
+            let value = map.entry(id(offset)).or_insert_with_key(|key| {
+                calls += 1;
+                format!("bucket {}", key.get_offset())
+            });
+            value.push('!');
+        }
+        assert_eq!(calls, 2);
+        assert_eq!(map.get(id(2)).map(String::as_str), Some("bucket 2!!"));
+        assert_eq!(map.get(id(7)).map(String::as_str), Some("bucket 7!"));
+        assert_eq!(map.len(), 2);
+    }
+
+    #[test]
+    fn occupied_entry_replace_entry() {
+        let mut map = BlazeMap::new();
+        map.insert(id(3), "old");
+        let Entry::Occupied(entry) = map.entry(id(3)) else {
+            panic!("entry must be occupied");
+        };
+        assert_eq!(entry.replace_entry("new"), (id(3), "old"));
+        assert_eq!(map.get(id(3)), Some(&"new"));
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn vacant_entry_into_key() {
+        let mut map = BlazeMap::<Id, usize>::new();
+        let Entry::Vacant(entry) = map.entry(id(5)) else {
+            panic!("entry must be vacant");
+        };
+        assert_eq!(entry.into_key(), id(5));
+        assert_eq!(map.len(), 0);
+        assert!(map.is_empty());
+        assert_eq!(map.get(id(5)), None);
+    }
+
+    #[test]
+    fn get_or_insert_with() {
+        let mut map = BlazeMap::new();
+        let mut calls = 0;
+        *map.get_or_insert_with(id(3), || {
+            calls += 1;
+            10
+        }) += 1;
+        assert_eq!(calls, 1);
+        *map.get_or_insert_with(id(3), || {
+            calls += 1;
+            20
+        }) += 1;
+        assert_eq!(calls, 1);
+        assert_eq!(map.get(id(3)), Some(&12));
+
+        assert_eq!(*map.get_or_insert(id(3), 30), 12);
+        assert_eq!(*map.get_or_insert(id(1), 40), 40);
+        assert_eq!(map.len(), 2);
+    }
+
+    #[test]
+    fn entry_or_insert_with_registers_keys() {
+        define_key_wrapper! {
+            struct Key(String);
+            Derive(as for Original Type): {
+                Debug
+            }
+        }
+
+        let mut map = BlazeMap::new();
+        let first = Key::new("first".to_string());
+        let value = map.entry(first).or_insert_with(|| {
+            let second = Key::new("second".to_string());
+            format!("{second:?}")
+        });
+        assert_eq!(value, r#"Key { original_key: "second", offset: 1 }"#);
+        map.insert(first, String::new());
+        let value = map.entry(Key::new("third".to_string())).or_insert_with(|| {
+            let fourth = Key::new("fourth".to_string());
+            format!("{first:?} {fourth:?}")
+        });
+        assert_eq!(
+            value,
+            r#"Key { original_key: "first", offset: 0 } Key { original_key: "fourth", offset: 3 }"#
+        );
+        assert_eq!(map.len(), 2);
+    }
+
+    #[test]
+    fn get_or_insert_with_key_and_offset() {
+        define_key_wrapper_bounded! {
+            struct Key(&'static str);
+            MAX_CAP = 2
+        }
+
+        let mut map = BlazeMap::new();
+        for name in ["first", "second", "first"] {
+            map.get_or_insert_with_key_and_offset(Key::new(name), |key, offset| {
+                format!("{}#{offset}", key.key())
+            })
+            .push('!');
+        }
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(Key::new("first")).unwrap(), "first#0!!");
+        assert_eq!(map.get(Key::new("second")).unwrap(), "second#1!");
+    }
+
+    #[test]
+    fn eq_hash_map() {
+        let map: BlazeMap<_, _> = [(id(1), "a"), (id(5), "b"), (id(2), "c")]
+            .into_iter()
+            .collect();
+        let mut expected: HashMap<_, _> = [(id(2), "c"), (id(1), "a"), (id(5), "b")]
+            .into_iter()
+            .collect();
+        assert_eq!(map, expected);
+        assert_eq!(expected, map);
+
+        expected.insert(id(5), "d");
+        assert_ne!(map, expected);
+        assert_ne!(expected, map);
+
+        expected.insert(id(5), "b");
+        expected.insert(id(7), "e");
+        assert_ne!(map, expected);
+        expected.remove(&id(7));
+        expected.remove(&id(1));
+        expected.insert(id(3), "a");
+        assert_ne!(map, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds maximum capacity")]
+    fn entry_insert_huge_offset() {
+        let mut map = BlazeMap::<Id, u64>::new();
+        map.entry(id(usize::MAX)).or_insert(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds maximum capacity")]
+    fn insert_near_max_offset() {
+        let mut map = BlazeMap::<Id, u64>::new();
+        map.insert(id(usize::MAX / 4), 0);
+    }
+
+    #[test]
+    fn extract_if() {
+        let mut map: BlazeMap<_, _> = (0..20).map(|offset| (id(offset), offset)).collect();
+        map.remove(id(4));
+        let mut extracted = map.extract_if(|key, value| {
+            *value += 100;
+            key.get_offset() % 2 == 0
+        });
+        assert_eq!(extracted.next(), Some((id(0), 100)));
+        assert_eq!(extracted.next(), Some((id(2), 102)));
+        assert_eq!(extracted.next(), Some((id(6), 106)));
+        drop(extracted);
+        assert_eq!(map.len(), 10);
+        for (key, value) in &map {
+            assert_eq!(key.get_offset() % 2, 1);
+            assert_eq!(*value, key.get_offset() + 100);
+        }
+
+        let extracted: Vec<_> = map
+            .extract_if(|key, _| key.get_offset() > 15)
+            .map(|(key, value)| (key.get_offset(), value))
+            .collect();
+        assert_eq!(extracted, [(17, 117), (19, 119)]);
+        assert_eq!(map.len(), 8);
+        assert_eq!(map.extract_if(|_, _| false).count(), 0);
+        assert_eq!(map.len(), 8);
+    }
+
+    #[test]
+    fn clone_from() {
+        let source: BlazeMap<_, _> = (0..50)
+            .filter(|offset| offset % 3 == 0)
+            .map(|offset| (id(offset), offset.to_string()))
+            .collect();
+        let mut target: BlazeMap<_, _> =
+            (0..100).map(|offset| (id(offset), String::new())).collect();
+        let capacity = target.inner.capacity();
+        let ptr = target.inner.as_ptr();
+        target.clone_from(&source);
+        assert_eq!(target.inner.capacity(), capacity);
+        assert_eq!(target.inner.as_ptr(), ptr);
+        assert_eq!(target.len(), source.len());
+        assert_eq!(
+            target.iter().collect::<Vec<_>>(),
+            source.iter().collect::<Vec<_>>()
+        );
+
+        let mut target = BlazeMap::new();
+        target.clone_from(&source);
+        assert_eq!(
+            target.iter().collect::<Vec<_>>(),
+            source.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn entry_get() {
+        let mut map = BlazeMap::new();
+        let mut entry = map.entry(id(2));
+        assert_eq!(entry.get(), None);
+        assert_eq!(entry.get_mut(), None);
+        entry.or_insert(5);
+
+        let mut entry = map.entry(id(2));
+        assert_eq!(entry.get(), Some(&5));
+        *entry.get_mut().unwrap() += 1;
+        assert_eq!(entry.get(), Some(&6));
+        assert!(matches!(entry, Entry::Occupied(_)));
+        assert_eq!(map.get(id(2)), Some(&6));
+    }
+
+    #[test]
+    fn append() {
+        let mut map: BlazeMap<_, _> = (0..10).map(|offset| (id(offset), offset)).collect();
+        let mut other: BlazeMap<_, _> = (5..20)
+            .step_by(2)
+            .map(|offset| (id(offset), offset * 10))
+            .collect();
+        let other_capacity = other.inner.capacity();
+        map.append(&mut other);
+        assert!(other.is_empty());
+        assert_eq!(other.inner.capacity(), other_capacity);
+        assert_eq!(map.len(), 15);
+        for (key, value) in &map {
+            let offset = key.get_offset();
+            if offset >= 5 && offset % 2 == 1 {
+                assert_eq!(*value, offset * 10);
+            } else {
+                assert_eq!(*value, offset);
+            }
+        }
+
+        let mut other: BlazeMap<_, _> = [(id(2), 0)].into_iter().collect();
+        other.append(&mut map);
+        assert!(map.is_empty());
+        assert_eq!(other.len(), 15);
+        assert_eq!(other.get(id(2)), Some(&2));
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn par_extend() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+        use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let pairs: Vec<_> = (0..10_000)
+            .map(|idx| (rng.gen_range(0..3_000), idx))
+            .collect();
+
+        let mut expected: BlazeMap<_, _> = (0..100).map(|offset| (id(offset * 7), 0)).collect();
+        let mut map = expected.clone();
+        for (offset, value) in &pairs {
+            expected.insert(id(*offset), *value);
+        }
+        map.par_extend(
+            pairs
+                .into_par_iter()
+                .map(|(offset, value)| (id(offset), value)),
+        );
+        assert_eq!(map.len(), expected.len());
+        assert_eq!(
+            map.iter().collect::<Vec<_>>(),
+            expected.iter().collect::<Vec<_>>()
+        );
+
+        map.par_extend(Vec::<(Id, usize)>::new());
+        assert_eq!(map.len(), expected.len());
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn parallel_extend_trait() {
+        use rayon::iter::{IntoParallelIterator, ParallelExtend, ParallelIterator};
+
+        fn extend_generic<C: ParallelExtend<(Id, usize)>>(collection: &mut C, len: usize) {
+            collection.par_extend(
+                (0..len)
+                    .into_par_iter()
+                    .map(|idx| (id(idx * 7 % 1_000), idx)),
+            );
+        }
+
+        let mut expected = BlazeMap::new();
+        for idx in 0..10_000 {
+            expected.insert(id(idx * 7 % 1_000), idx);
+        }
+        let mut map = BlazeMap::new();
+        extend_generic(&mut map, 10_000);
+        assert_eq!(map.len(), 1_000);
+        assert_eq!(map, expected);
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn par_iter() {
+        use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+        let mut map: BlazeMap<_, _> = (0..10_000)
+            .filter(|offset| offset % 3 != 0)
+            .map(|offset| (id(offset), offset))
+            .collect();
+        let expected: usize = map
+            .iter()
+            .map(|(key, value)| key.get_offset() + value)
+            .sum();
+        let sum: usize = (&map)
+            .into_par_iter()
+            .map(|(key, value)| key.get_offset() + value)
+            .sum();
+        assert_eq!(sum, expected);
+        assert_eq!((&map).into_par_iter().count(), map.len());
+
+        (&mut map).into_par_iter().for_each(|(key, value)| {
+            *value += key.get_offset();
+        });
+        let expected: usize = map.values().sum();
+        assert_eq!(expected, 2 * map.keys().map(Id::get_offset).sum::<usize>());
+
+        let mut pairs: Vec<_> = map.clone().into_par_iter().collect();
+        pairs.sort_unstable_by_key(|(key, _)| key.get_offset());
+        assert_eq!(
+            pairs,
+            map.iter()
+                .map(|(key, value)| (key, *value))
+                .collect::<Vec<_>>()
+        );
+        let sum: usize = map.into_par_iter().map(|(_, value)| value).sum();
+        assert_eq!(sum, expected);
+    }
+
+    #[test]
+    fn entry_or_insert_or_max_min() {
+        let mut map = BlazeMap::new();
+        assert_eq!(*map.entry(id(0)).or_insert_or_max(5), 5);
+        assert_eq!(*map.entry(id(0)).or_insert_or_max(3), 5);
+        assert_eq!(*map.entry(id(0)).or_insert_or_max(5), 5);
+        assert_eq!(*map.entry(id(0)).or_insert_or_max(8), 8);
+
+        assert_eq!(*map.entry(id(1)).or_insert_or_min(5), 5);
+        assert_eq!(*map.entry(id(1)).or_insert_or_min(8), 5);
+        assert_eq!(*map.entry(id(1)).or_insert_or_min(3), 3);
+        *map.entry(id(1)).or_insert_or_min(3) -= 1;
+
+        assert_eq!(map.get(id(0)), Some(&8));
+        assert_eq!(map.get(id(1)), Some(&2));
+        assert_eq!(map.len(), 2);
+    }
+
+    #[test]
+    fn value_chunks_mut() {
+        let mut map = BlazeMap::new();
+        for offset in (0..103).filter(|offset| offset % 3 != 0) {
+            map.insert(id(offset), offset);
+        }
+        let chunks = map.value_chunks_mut(4);
+        assert_eq!(chunks.len(), 4);
+        assert_eq!(
+            chunks
+                .iter()
+                .map(ValuesMutChunk::base_offset)
+                .collect::<Vec<_>>(),
+            [0, 26, 52, 78]
+        );
+        std::thread::scope(|s| {
+            for chunk in chunks {
+                s.spawn(move || {
+                    let base_offset = chunk.base_offset();
+                    for value in chunk {
+                        assert!((base_offset..base_offset + 26).contains(value));
+                        *value *= 10;
+                    }
+                });
+            }
+        });
+        assert_eq!(map.len(), 68);
+        for (key, value) in &map {
+            assert_eq!(*value, key.get_offset() * 10);
+        }
+
+        let mut map = BlazeMap::<Id, usize>::new();
+        map.insert(id(1), 1);
+        let chunks = map.value_chunks_mut(4);
+        assert_eq!(chunks.len(), 4);
+        assert_eq!(chunks.into_iter().flatten().count(), 1);
+    }
+
+    #[test]
+    fn from_keys_with() {
+        define_key_wrapper! {
+            struct Key(String)
+        }
+
+        let names = ["first", "second", "third"];
+        for name in names {
+            let _ = Key::new(name);
+        }
+        let map = BlazeMap::from_keys_with(|key: Key| key.get_offset() * 10);
+        assert_eq!(map.len(), Key::num_registered());
+        assert_eq!(map.len(), names.len());
+        for name in names {
+            let key = Key::new(name);
+            assert_eq!(map.get(key), Some(&(key.get_offset() * 10)));
+        }
+    }
+
+    #[test]
+    fn remap_keys() {
+        define_key_wrapper! {
+            struct KeyA(String)
+        }
+        define_key_wrapper! {
+            struct KeyB(String)
+        }
+
+        // Make `KeyB` offsets differ from `KeyA` ones.
+        let _ = KeyB::new("third".to_string());
+
+        let mut map = BlazeMap::new();
+        map.insert(KeyA::new("first".to_string()), 1);
+        map.insert(KeyA::new("second".to_string()), 2);
+        map.insert(KeyA::new("third".to_string()), 3);
+        map.remove(KeyA::new("second".to_string()));
+
+        let map: BlazeMap<KeyB, _> = map.remap_keys();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(KeyB::new("first".to_string())), Some(&1));
+        assert_eq!(map.get(KeyB::new("second".to_string())), None);
+        assert_eq!(map.get(KeyB::new("third".to_string())), Some(&3));
+
+        let map: BlazeMap<KeyB, _> = map.remap_keys();
+        assert_eq!(map.get(KeyB::new("first".to_string())), Some(&1));
+        assert_eq!(map.get(KeyB::new("third".to_string())), Some(&3));
+    }
+
+    #[test]
+    fn entry_key_orig() {
+        define_key_wrapper! {
+            struct Key(String)
+        }
+
+        let mut map = BlazeMap::new();
+        map.insert(Key::new("first".to_string()), 1);
+
+        let entry = map.entry(Key::new("first".to_string()));
+        assert!(matches!(entry, Entry::Occupied(_)));
+        assert_eq!(entry.key_orig().borrow(), "first");
+
+        let entry = map.entry(Key::new("second".to_string()));
+        assert!(matches!(entry, Entry::Vacant(_)));
+        assert_eq!(entry.key_orig().borrow(), "second");
+    }
+}
@@ -0,0 +1,177 @@
+use crate::{
+    collections::map::BlazeMap,
+    prelude::{BlazeMapId, BlazeMapIdStatic, BlazeMapIdWrapper},
+    traits::{KeyByOffsetProvider, TypeInfoContainer},
+};
+use core::{
+    borrow::Borrow,
+    fmt::{Debug, Formatter},
+    marker::PhantomData,
+};
+use rkyv::{
+    munge::munge,
+    rancor::Fallible,
+    ser::{Allocator, Writer},
+    vec::{ArchivedVec, VecResolver},
+    Archive, Archived, Deserialize, Place, Portable, Serialize,
+};
+
+/// An archived [`BlazeMap`].
+///
+/// Stores the flat vector of values directly, so that the offsets of the
+/// keys are the natural indices of the archived values,
+/// along with a side table of the original keys for every offset.
+#[derive(Portable, rkyv::bytecheck::CheckBytes)]
+#[bytecheck(crate = rkyv::bytecheck)]
+#[repr(C)]
+pub struct ArchivedBlazeMap<K, V>
+where
+    K: BlazeMapId,
+    K::OrigType: Archive,
+    V: Archive,
+{
+    values: ArchivedVec<Archived<Option<V>>>,
+    keys: ArchivedVec<Archived<K::OrigType>>,
+    len: Archived<usize>,
+    phantom: PhantomData<K>,
+}
+
+/// The resolver for an archived [`BlazeMap`].
+pub struct BlazeMapResolver {
+    values: VecResolver,
+    keys: VecResolver,
+}
+
+impl<K, V> ArchivedBlazeMap<K, V>
+where
+    K: BlazeMapId,
+    K::OrigType: Archive,
+    V: Archive,
+{
+    /// Returns the number of elements in the archived map.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len.to_native() as usize
+    }
+
+    /// Returns `true` if the archived map contains no elements.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a reference to the archived value corresponding to the key.
+    ///
+    /// The lookup is performed by the offset of the key,
+    /// so it's only meaningful if the keys were registered in the same order
+    /// as in the process that archived the map, e.g. within the same process.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, key: K) -> Option<&Archived<V>> {
+        self.values.get(key.get_offset())?.as_ref()
+    }
+
+    /// An iterator visiting all archived original key-value pairs
+    /// in the order of their offsets.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (&Archived<K::OrigType>, &Archived<V>)> {
+        self.keys
+            .iter()
+            .zip(self.values.iter())
+            .filter_map(|(key, value)| Some((key, value.as_ref()?)))
+    }
+}
+
+impl<K, V> Archive for BlazeMap<K, V>
+where
+    K: BlazeMapId,
+    K::OrigType: Archive,
+    V: Archive,
+{
+    type Archived = ArchivedBlazeMap<K, V>;
+    type Resolver = BlazeMapResolver;
+
+    #[inline]
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        munge!(let ArchivedBlazeMap { values, keys, len, .. } = out);
+        ArchivedVec::resolve_from_len(self.inner.len(), resolver.values, values);
+        ArchivedVec::resolve_from_len(self.inner.len(), resolver.keys, keys);
+        self.len.resolve((), len);
+    }
+}
+
+impl<K, V, S> Serialize<S> for BlazeMap<K, V>
+where
+    K: BlazeMapIdStatic,
+    K::OrigType: Serialize<S>,
+    V: Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
+{
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        debug_assert_eq!(
+            self.inner.iter().filter_map(Option::as_ref).count(),
+            self.len
+        );
+        let keys: Vec<K::OrigType> = {
+            let guard = K::static_container().key_by_offset_provider();
+            (0..self.inner.len())
+                .map(|offset| unsafe { guard.key_by_offset_unchecked(offset).borrow().clone() })
+                .collect()
+        };
+        Ok(BlazeMapResolver {
+            values: ArchivedVec::serialize_from_slice(&self.inner, serializer)?,
+            keys: ArchivedVec::serialize_from_slice(&keys, serializer)?,
+        })
+    }
+}
+
+impl<K, V, D> Deserialize<BlazeMap<K, V>, D> for ArchivedBlazeMap<K, V>
+where
+    K: BlazeMapIdWrapper + BlazeMapIdStatic,
+    K::OrigType: Archive,
+    Archived<K::OrigType>: Deserialize<K::OrigType, D>,
+    V: Archive,
+    Archived<V>: Deserialize<V, D>,
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize(&self, deserializer: &mut D) -> Result<BlazeMap<K, V>, D::Error> {
+        let mut result = BlazeMap::with_current_key_type_capacity();
+        for (key, value) in self.iter() {
+            let key = key.deserialize(deserializer)?;
+            let value = value.deserialize(deserializer)?;
+            let key = unsafe { K::new(K::static_container(), key) };
+            result.insert(key, value);
+        }
+        result.shrink_to_fit();
+        debug_assert_eq!(
+            result.inner.iter().filter_map(Option::as_ref).count(),
+            result.len
+        );
+        Ok(result)
+    }
+}
+
+impl<K, V> Debug for ArchivedBlazeMap<K, V>
+where
+    K: BlazeMapId,
+    K::OrigType: Archive,
+    Archived<K::OrigType>: Debug,
+    V: Archive,
+    Archived<V>: Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl Debug for BlazeMapResolver {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("BlazeMapResolver").finish_non_exhaustive()
+    }
+}
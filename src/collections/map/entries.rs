@@ -1,6 +1,14 @@
-use crate::prelude::BlazeMapId;
+use crate::{
+    prelude::{BlazeMapId, BlazeMapIdStatic},
+    traits::{KeyByOffsetProvider, TypeInfoContainer},
+};
+use alloc::vec::Vec;
+use core::{
+    borrow::Borrow,
+    fmt::{Debug, Formatter},
+    ops::{Deref, DerefMut},
+};
 
-#[derive(Debug)]
 /// A view into a single entry in a map, which may either be vacant or occupied.
 ///
 /// This enum is constructed
@@ -18,7 +26,6 @@ where
     Vacant(VacantEntry<'a, K, V>),
 }
 
-#[derive(Debug)]
 /// A view into an occupied entry in a
 /// [`BlazeMap`](crate::collections::map::BlazeMap). It is part of the
 /// [`Entry`] enum.
@@ -33,7 +40,6 @@ where
     pub(in crate::collections::map) value: &'a mut Option<V>,
 }
 
-#[derive(Debug)]
 /// A view into a vacant entry in a
 /// [`BlazeMap`](crate::collections::map::BlazeMap). It is part of the
 /// [`Entry`] enum.
@@ -48,12 +54,66 @@ where
     pub(in crate::collections::map) inner: VacantEntryInner<'a, V>,
 }
 
+#[derive(Debug)]
+/// A mutable reference to a value in a
+/// [`BlazeMap`](crate::collections::map::BlazeMap) that raises a dirty flag
+/// whenever the value is accessed mutably.
+///
+/// This `struct` is created by the [`or_insert_tracked`] method on [`Entry`].
+///
+/// [`or_insert_tracked`]: Entry::or_insert_tracked
+pub struct TrackedMut<'a, V> {
+    value: &'a mut V,
+
+    dirty: &'a mut bool,
+}
+
 #[derive(Debug)]
 pub(in crate::collections::map) enum VacantEntryInner<'a, V> {
     ShouldBeInserted(&'a mut Option<V>),
     ShouldBeEnlarged(&'a mut Vec<Option<V>>),
 }
 
+impl<K, V> Debug for Entry<'_, K, V>
+where
+    K: BlazeMapId,
+    V: Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Entry::Occupied(entry) => f.debug_tuple("Entry").field(entry).finish(),
+            Entry::Vacant(entry) => f.debug_tuple("Entry").field(entry).finish(),
+        }
+    }
+}
+
+impl<K, V> Debug for OccupiedEntry<'_, K, V>
+where
+    K: BlazeMapId,
+    V: Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("OccupiedEntry")
+            .field("offset", &self.key.get_offset())
+            .field("value", self.get())
+            .finish()
+    }
+}
+
+impl<K, V> Debug for VacantEntry<'_, K, V>
+where
+    K: BlazeMapId,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("VacantEntry")
+            .field("offset", &self.key.get_offset())
+            .finish()
+    }
+}
+
 impl<'a, K, V> Entry<'a, K, V>
 where
     K: BlazeMapId,
@@ -79,6 +139,60 @@ where
         }
     }
 
+    /// Ensures a value is in the entry by inserting the result of the
+    /// fallible default function if empty, and returns a mutable reference
+    /// to the value in the entry.
+    ///
+    /// # Errors
+    /// Returns the error of the default function if it fails,
+    /// leaving the map unchanged.
+    #[inline]
+    pub fn or_try_insert_with<F, E>(self, default: F) -> Result<&'a mut V, E>
+    where
+        F: FnOnce() -> Result<V, E>,
+    {
+        match self {
+            Entry::Occupied(entry) => Ok(entry.into_mut()),
+            Entry::Vacant(entry) => Ok(entry.insert(default()?)),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting, if empty, the result of
+    /// the default function called with the entry’s key, and returns a mutable
+    /// reference to the value in the entry.
+    #[inline]
+    pub fn or_insert_with_key(self, default: impl FnOnce(K) -> V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let value = default(entry.key());
+                entry.insert(value)
+            }
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the result of the default
+    /// function if empty, and returns a [`TrackedMut`] guard over the value
+    /// in the entry.
+    ///
+    /// The guard sets `dirty` to `true` on every mutable access to the value.
+    /// Note that the insertion of the default value itself doesn't raise the
+    /// flag.
+    #[inline]
+    pub fn or_insert_tracked<'b>(
+        self,
+        default: impl FnOnce() -> V,
+        dirty: &'b mut bool,
+    ) -> TrackedMut<'b, V>
+    where
+        'a: 'b,
+    {
+        TrackedMut {
+            value: self.or_insert_with(default),
+            dirty,
+        }
+    }
+
     /// Returns this entry’s key.
     #[inline]
     #[must_use]
@@ -89,6 +203,27 @@ where
         }
     }
 
+    /// Returns a reference to the value of an occupied entry,
+    /// or `None` if the entry is vacant.
+    #[inline]
+    #[must_use]
+    pub fn get(&self) -> Option<&V> {
+        match self {
+            Entry::Occupied(entry) => Some(entry.get()),
+            Entry::Vacant(_) => None,
+        }
+    }
+
+    /// Returns a mutable reference to the value of an occupied entry,
+    /// or `None` if the entry is vacant.
+    #[inline]
+    pub fn get_mut(&mut self) -> Option<&mut V> {
+        match self {
+            Entry::Occupied(entry) => Some(entry.get_mut()),
+            Entry::Vacant(_) => None,
+        }
+    }
+
     /// Provides in-place mutable access
     /// to an occupied entry before any potential inserts into the map.
     #[inline]
@@ -102,6 +237,100 @@ where
             Entry::Vacant(entry) => Entry::Vacant(entry),
         }
     }
+
+    /// Provides shared access to the key and owned access to the value of
+    /// an occupied entry and allows to replace or remove it based on the
+    /// value of the returned option.
+    ///
+    /// If `f` returns `None`, the entry is removed from the map
+    /// and an [`Entry::Vacant`] is returned.
+    /// Does nothing for a vacant entry.
+    #[inline]
+    #[must_use]
+    pub fn and_replace_entry_with<F>(self, f: F) -> Self
+    where
+        F: FnOnce(K, V) -> Option<V>,
+    {
+        match self {
+            Entry::Occupied(OccupiedEntry { key, len, value }) => {
+                // The entry is removed before calling `f`,
+                // so that the map stays consistent if `f` panics.
+                let old = unsafe { value.take().unwrap_unchecked() };
+                *len -= 1;
+                if let Some(new) = f(key, old) {
+                    *value = Some(new);
+                    *len += 1;
+                    Entry::Occupied(OccupiedEntry { key, len, value })
+                } else {
+                    Entry::Vacant(VacantEntry {
+                        key,
+                        len,
+                        inner: VacantEntryInner::ShouldBeInserted(value),
+                    })
+                }
+            }
+            Entry::Vacant(entry) => Entry::Vacant(entry),
+        }
+    }
+}
+
+impl<'a, K, V> Entry<'a, K, V>
+where
+    K: BlazeMapId,
+    V: Ord,
+{
+    /// Ensures a value is in the entry by inserting `candidate` if empty,
+    /// or by replacing the existing value with `candidate` if the latter is
+    /// greater, and returns a mutable reference to the value in the entry.
+    #[inline]
+    pub fn or_insert_or_max(self, candidate: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => {
+                let value = entry.into_mut();
+                if candidate > *value {
+                    *value = candidate;
+                }
+                value
+            }
+            Entry::Vacant(entry) => entry.insert(candidate),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting `candidate` if empty,
+    /// or by replacing the existing value with `candidate` if the latter is
+    /// less, and returns a mutable reference to the value in the entry.
+    #[inline]
+    pub fn or_insert_or_min(self, candidate: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => {
+                let value = entry.into_mut();
+                if candidate < *value {
+                    *value = candidate;
+                }
+                value
+            }
+            Entry::Vacant(entry) => entry.insert(candidate),
+        }
+    }
+}
+
+impl<K, V> Entry<'_, K, V>
+where
+    K: BlazeMapIdStatic,
+{
+    /// Returns the original key corresponding to this entry’s key.
+    ///
+    /// The original key is cloned under a short-lived read lock on the `K`
+    /// registry, so holding the result doesn't block the registration of new
+    /// keys.
+    #[inline]
+    #[must_use]
+    pub fn key_orig(&self) -> impl Borrow<K::OrigType> {
+        let guard = K::static_container().key_by_offset_provider();
+        let key = unsafe { guard.key_by_offset_unchecked(self.key().get_offset()) };
+        let orig: K::OrigType = key.borrow().clone();
+        orig
+    }
 }
 
 impl<'a, K, V> Entry<'a, K, V>
@@ -176,7 +405,28 @@ where
     /// Sets the value of the entry, and returns the entry’s old value.
     #[inline]
     pub fn insert(&mut self, value: V) -> V {
-        std::mem::replace(self.get_mut(), value)
+        core::mem::replace(self.get_mut(), value)
+    }
+
+    /// Sets the value of the entry, and returns the entry’s old value
+    /// along with a mutable reference to the new one.
+    #[inline]
+    pub fn insert_get(&mut self, value: V) -> (V, &mut V) {
+        let current = self.get_mut();
+        let old = core::mem::replace(current, value);
+        (old, current)
+    }
+
+    /// Replaces the value of the entry, and returns the entry’s key along with
+    /// its old value.
+    ///
+    /// Since the keys are [`Copy`] identifiers, the key itself isn't replaced;
+    /// the method exists for parity with the `std` entry API.
+    #[inline]
+    pub fn replace_entry(self, value: V) -> (K, V) {
+        let key = self.key;
+        let old = core::mem::replace(self.into_mut(), value);
+        (key, old)
     }
 
     /// Takes the value out of the entry, and returns it.
@@ -200,22 +450,54 @@ where
         self.key
     }
 
+    /// Takes ownership of the key, leaving the map unchanged.
+    #[inline]
+    #[must_use]
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
     /// Sets the value of the entry with the [`VacantEntry`]’s key,
     /// and returns a mutable reference to it.
+    ///
+    /// # Panics
+    /// Panics if the offset of the key exceeds the maximum capacity
+    /// of the [`BlazeMap`](crate::prelude::BlazeMap).
     #[inline]
     pub fn insert(self, value: V) -> &'a mut V {
         let Self { key, len, inner } = self;
-        *len += 1;
         let reference = match inner {
             VacantEntryInner::ShouldBeInserted(reference) => reference,
             VacantEntryInner::ShouldBeEnlarged(vec) => {
                 let offset = key.get_offset();
-                let new_len = offset + 1; // It's safe to don't use `checked_add`, since `vec` will panic at `isize::MAX`
-                vec.resize_with(new_len, || None);
+                let max_len = isize::MAX as usize / core::mem::size_of::<Option<V>>().max(1);
+                assert!(
+                    offset < max_len,
+                    "BlazeMap offset {offset} exceeds maximum capacity"
+                );
+                vec.resize_with(offset + 1, || None);
                 unsafe { vec.get_unchecked_mut(offset) }
             }
         };
+        *len += 1;
         *reference = Some(value);
         unsafe { reference.as_mut().unwrap_unchecked() }
     }
 }
+
+impl<V> Deref for TrackedMut<'_, V> {
+    type Target = V;
+
+    #[inline]
+    fn deref(&self) -> &V {
+        self.value
+    }
+}
+
+impl<V> DerefMut for TrackedMut<'_, V> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut V {
+        *self.dirty = true;
+        self.value
+    }
+}
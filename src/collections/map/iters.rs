@@ -3,11 +3,12 @@ use crate::{
     prelude::{BlazeMapId, BlazeMapIdStatic},
     traits::{KeyByOffsetProvider, TypeInfoContainer},
 };
-use std::{
+use core::{
     borrow::Borrow,
     fmt::{Debug, Formatter},
     marker::PhantomData,
     panic::{RefUnwindSafe, UnwindSafe},
+    ptr::NonNull,
 };
 
 /// An iterator over the entries of a [`BlazeMap`].
@@ -72,6 +73,21 @@ pub struct ValuesMut<'a, K, V> {
     pub(in crate::collections::map) inner: IterMut<'a, K, V>,
 }
 
+/// A mutable iterator over the values of a contiguous range of offsets
+/// of a [`BlazeMap`].
+///
+/// This `struct` is created by the [`value_chunks_mut`] method on [`BlazeMap`].
+/// See its documentation for more.
+///
+/// [`value_chunks_mut`]: BlazeMap::value_chunks_mut
+pub struct ValuesMutChunk<'a, K, V> {
+    pub(in crate::collections::map) inner: core::slice::IterMut<'a, Option<V>>,
+
+    pub(in crate::collections::map) base_offset: usize,
+
+    pub(in crate::collections::map) phantom: PhantomData<K>,
+}
+
 /// An owning iterator over the entries of a [`BlazeMap`].
 ///
 /// This `struct` is created by the [`into_iter`] method on [`BlazeMap`]
@@ -112,6 +128,42 @@ pub struct Drain<'a, K, V> {
     pub(in crate::collections::map) map: &'a mut BlazeMap<K, V>,
 
     pub(in crate::collections::map) current_position: usize,
+
+    pub(in crate::collections::map) end_position: usize,
+}
+
+/// A draining iterator over the entries of a [`BlazeMap`] whose key offsets
+/// fall within a range.
+///
+/// This `struct` is created by the [`drain_range`] method on [`BlazeMap`].
+/// See its documentation for more.
+///
+/// [`drain_range`]: BlazeMap::drain_range
+pub struct DrainRange<'a, K, V> {
+    pub(in crate::collections::map) map: &'a mut BlazeMap<K, V>,
+
+    pub(in crate::collections::map) current_position: usize,
+
+    pub(in crate::collections::map) end_position: usize,
+}
+
+/// An iterator that removes and yields the entries of a [`BlazeMap`]
+/// matching a predicate.
+///
+/// This `struct` is created by the [`extract_if`] method on [`BlazeMap`]. See
+/// its documentation for more.
+///
+/// [`extract_if`]: BlazeMap::extract_if
+pub struct ExtractIf<'a, K, V, F>
+where
+    K: BlazeMapId,
+    F: FnMut(K, &mut V) -> bool,
+{
+    pub(in crate::collections::map) map: &'a mut BlazeMap<K, V>,
+
+    pub(in crate::collections::map) pred: F,
+
+    pub(in crate::collections::map) current_position: usize,
 }
 
 impl<'a, K, V> Iterator for Iter<'a, K, V>
@@ -136,7 +188,6 @@ where
                 match &*inner.add(*current_position) {
                     None => {
                         *current_position += 1;
-                        continue;
                     }
                     Some(value) => {
                         let key = K::from_offset_unchecked(*current_position);
@@ -150,7 +201,7 @@ where
     }
 }
 
-impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V>
+impl<K, V> ExactSizeIterator for Iter<'_, K, V>
 where
     K: BlazeMapId,
 {
@@ -176,7 +227,6 @@ where
                 match &mut *self.inner.add(self.current_position) {
                     None => {
                         self.current_position += 1;
-                        continue;
                     }
                     Some(value) => {
                         let key = K::from_offset_unchecked(self.current_position);
@@ -190,7 +240,7 @@ where
     }
 }
 
-impl<'a, K, V> ExactSizeIterator for IterMut<'a, K, V>
+impl<K, V> ExactSizeIterator for IterMut<'_, K, V>
 where
     K: BlazeMapId,
 {
@@ -200,7 +250,48 @@ where
     }
 }
 
-impl<'a, K, V> Iterator for Keys<'a, K, V>
+impl<'a, K, V> IterMut<'a, K, V> {
+    /// Splits the remaining entries into two non-overlapping iterators:
+    /// the first one yields the entries whose key offsets are less than
+    /// `offset`, and the second one yields the rest.
+    ///
+    /// Note that the split is performed by the offset of the keys,
+    /// not by the number of the entries.
+    /// Both halves can be sent to different threads.
+    #[inline]
+    #[must_use]
+    pub fn split_at_offset(self, offset: usize) -> (IterMut<'a, K, V>, IterMut<'a, K, V>) {
+        let Self {
+            inner,
+            current_position,
+            len,
+            ..
+        } = self;
+        let mut position = current_position;
+        let mut head_len = 0;
+        while head_len != len && position < offset {
+            if unsafe { (*inner.add(position)).is_some() } {
+                head_len += 1;
+            }
+            position += 1;
+        }
+        let head = IterMut {
+            inner,
+            current_position,
+            len: head_len,
+            phantom: PhantomData,
+        };
+        let tail = IterMut {
+            inner,
+            current_position: position,
+            len: len - head_len,
+            phantom: PhantomData,
+        };
+        (head, tail)
+    }
+}
+
+impl<K, V> Iterator for Keys<'_, K, V>
 where
     K: BlazeMapId,
 {
@@ -219,24 +310,19 @@ where
         }
         unsafe {
             loop {
-                match &*inner.add(*current_position) {
-                    None => {
-                        *current_position += 1;
-                        continue;
-                    }
-                    Some(_) => {
-                        let key = K::from_offset_unchecked(*current_position);
-                        *current_position += 1;
-                        *len -= 1;
-                        return Some(key);
-                    }
+                if (*inner.add(*current_position)).is_some() {
+                    let key = K::from_offset_unchecked(*current_position);
+                    *current_position += 1;
+                    *len -= 1;
+                    return Some(key);
                 }
+                *current_position += 1;
             }
         }
     }
 }
 
-impl<'a, K, V> ExactSizeIterator for Keys<'a, K, V>
+impl<K, V> ExactSizeIterator for Keys<'_, K, V>
 where
     K: BlazeMapId,
 {
@@ -264,7 +350,6 @@ impl<'a, K, V> Iterator for Values<'a, K, V> {
             match unsafe { &*inner.add(*current_position) } {
                 None => {
                     *current_position += 1;
-                    continue;
                 }
                 Some(value) => {
                     *current_position += 1;
@@ -276,7 +361,7 @@ impl<'a, K, V> Iterator for Values<'a, K, V> {
     }
 }
 
-impl<'a, K, V> ExactSizeIterator for Values<'a, K, V> {
+impl<K, V> ExactSizeIterator for Values<'_, K, V> {
     #[inline]
     fn len(&self) -> usize {
         self.inner.len
@@ -296,7 +381,6 @@ impl<'a, K, V> Iterator for ValuesMut<'a, K, V> {
             match unsafe { &mut *inner.inner.add(inner.current_position) } {
                 None => {
                     inner.current_position += 1;
-                    continue;
                 }
                 Some(value) => {
                     inner.current_position += 1;
@@ -308,13 +392,36 @@ impl<'a, K, V> Iterator for ValuesMut<'a, K, V> {
     }
 }
 
-impl<'a, K, V> ExactSizeIterator for ValuesMut<'a, K, V> {
+impl<K, V> ExactSizeIterator for ValuesMut<'_, K, V> {
     #[inline]
     fn len(&self) -> usize {
         self.inner.len
     }
 }
 
+impl<'a, K, V> Iterator for ValuesMutChunk<'a, K, V> {
+    type Item = &'a mut V;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a mut V> {
+        self.inner.by_ref().find_map(Option::as_mut)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.inner.len()))
+    }
+}
+
+impl<K, V> ValuesMutChunk<'_, K, V> {
+    /// Returns the offset of the first key covered by this chunk.
+    #[inline]
+    #[must_use]
+    pub fn base_offset(&self) -> usize {
+        self.base_offset
+    }
+}
+
 impl<K, V> Iterator for IntoIter<K, V>
 where
     K: BlazeMapId,
@@ -345,6 +452,22 @@ where
     }
 }
 
+impl<K, V> IntoIter<K, V> {
+    /// Converts the iterator back into a [`BlazeMap`]
+    /// containing the entries that haven't been yielded yet.
+    /// Keeps the allocated memory for reuse.
+    #[inline]
+    #[must_use]
+    pub fn into_map(self) -> BlazeMap<K, V> {
+        let Self { inner } = self;
+        debug_assert_eq!(
+            inner.inner.iter().filter_map(Option::as_ref).count(),
+            inner.len
+        );
+        inner
+    }
+}
+
 impl<K, V> Iterator for IntoKeys<K, V>
 where
     K: BlazeMapId,
@@ -401,7 +524,7 @@ where
     }
 }
 
-impl<'a, K, V> Iterator for Drain<'a, K, V>
+impl<K, V> Iterator for Drain<'_, K, V>
 where
     K: BlazeMapId,
 {
@@ -418,7 +541,6 @@ where
                 match value.take() {
                     None => {
                         self.current_position += 1;
-                        continue;
                     }
                     Some(value) => {
                         let key = K::from_offset_unchecked(self.current_position);
@@ -432,7 +554,30 @@ where
     }
 }
 
-impl<'a, K, V> ExactSizeIterator for Drain<'a, K, V>
+impl<K, V> DoubleEndedIterator for Drain<'_, K, V>
+where
+    K: BlazeMapId,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<(K, V)> {
+        if self.map.len == 0 {
+            return None;
+        }
+        unsafe {
+            loop {
+                self.end_position -= 1;
+                let value = &mut *self.map.inner.as_mut_ptr().add(self.end_position);
+                if let Some(value) = value.take() {
+                    let key = K::from_offset_unchecked(self.end_position);
+                    self.map.len -= 1;
+                    return Some((key, value));
+                }
+            }
+        }
+    }
+}
+
+impl<K, V> ExactSizeIterator for Drain<'_, K, V>
 where
     K: BlazeMapId,
 {
@@ -442,53 +587,140 @@ where
     }
 }
 
-impl<'a, K, V> Drop for Drain<'a, K, V> {
+impl<K, V> Drop for Drain<'_, K, V> {
     #[inline]
     fn drop(&mut self) {
         self.map.clear();
     }
 }
 
-unsafe impl<'a, K, V> Send for Iter<'a, K, V>
+impl<K, V> Iterator for DrainRange<'_, K, V>
+where
+    K: BlazeMapId,
+{
+    type Item = (K, V);
+
+    #[inline]
+    fn next(&mut self) -> Option<(K, V)> {
+        while self.current_position < self.end_position {
+            let offset = self.current_position;
+            self.current_position += 1;
+            let slot = unsafe { self.map.inner.get_unchecked_mut(offset) };
+            if let Some(value) = slot.take() {
+                self.map.len -= 1;
+                return Some((unsafe { K::from_offset_unchecked(offset) }, value));
+            }
+        }
+        None
+    }
+}
+
+impl<K, V> Drop for DrainRange<'_, K, V> {
+    #[inline]
+    fn drop(&mut self) {
+        let inner = &mut self.map.inner;
+        for slot in &mut inner[self.current_position..self.end_position] {
+            if slot.take().is_some() {
+                self.map.len -= 1;
+            }
+        }
+        if self.end_position == inner.len() {
+            let new_len = inner
+                .iter()
+                .rposition(Option::is_some)
+                .map_or(0, |last_offset| last_offset + 1);
+            inner.truncate(new_len);
+        }
+        debug_assert_eq!(
+            self.map.inner.iter().filter_map(Option::as_ref).count(),
+            self.map.len
+        );
+    }
+}
+
+impl<K, V, F> Iterator for ExtractIf<'_, K, V, F>
+where
+    K: BlazeMapId,
+    F: FnMut(K, &mut V) -> bool,
+{
+    type Item = (K, V);
+
+    #[inline]
+    fn next(&mut self) -> Option<(K, V)> {
+        while let Some(slot) = self.map.inner.get_mut(self.current_position) {
+            let key = unsafe { K::from_offset_unchecked(self.current_position) };
+            self.current_position += 1;
+            if slot.as_mut().is_some_and(|value| (self.pred)(key, value)) {
+                self.map.len -= 1;
+                return slot.take().map(|value| (key, value));
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.map.len))
+    }
+}
+
+impl<K, V, F> Drop for ExtractIf<'_, K, V, F>
+where
+    K: BlazeMapId,
+    F: FnMut(K, &mut V) -> bool,
+{
+    #[inline]
+    fn drop(&mut self) {
+        self.for_each(drop);
+    }
+}
+
+unsafe impl<K, V> Send for Iter<'_, K, V>
 where
     K: Sync,
     V: Sync,
 {
 }
 
-unsafe impl<'a, K, V> Sync for Iter<'a, K, V>
+unsafe impl<K, V> Sync for Iter<'_, K, V>
 where
     K: Sync,
     V: Sync,
 {
 }
 
-impl<'a, K, V> Unpin for Iter<'a, K, V> {}
+impl<K, V> Unpin for Iter<'_, K, V> {}
 
-impl<'a, K, V> UnwindSafe for Iter<'a, K, V>
+impl<K, V> UnwindSafe for Iter<'_, K, V>
 where
     K: RefUnwindSafe,
     V: RefUnwindSafe,
 {
 }
 
-unsafe impl<'a, K, V> Send for IterMut<'a, K, V>
+unsafe impl<K, V> Send for IterMut<'_, K, V>
 where
     K: Sync,
     V: Send,
 {
 }
 
-unsafe impl<'a, K, V> Sync for IterMut<'a, K, V>
+unsafe impl<K, V> Sync for IterMut<'_, K, V>
 where
     K: Sync,
     V: Sync,
 {
 }
 
-impl<'a, K, V> Unpin for IterMut<'a, K, V> {}
+impl<K, V> Unpin for IterMut<'_, K, V> {}
 
-impl<'a, K, V> Clone for Iter<'a, K, V> {
+// `IntoIter`, `IntoKeys`, `IntoValues` and `Drain` hold no raw pointers:
+// they own or mutably borrow a `BlazeMap`, whose only `K`-dependent field is
+// a `PhantomData<K>`. Hence their auto-derived `Send` impls require exactly
+// `K: Send, V: Send`, and their auto-derived `Sync` impls require exactly
+// `K: Sync, V: Sync`, as they should, so explicit `unsafe` impls aren't needed.
+
+impl<K, V> Clone for Iter<'_, K, V> {
     #[inline]
     fn clone(&self) -> Self {
         Self {
@@ -500,14 +732,14 @@ impl<'a, K, V> Clone for Iter<'a, K, V> {
     }
 }
 
-impl<'a, K, V> Debug for Iter<'a, K, V>
+impl<K, V> Debug for Iter<'_, K, V>
 where
     K: BlazeMapIdStatic,
     K::OrigType: Debug,
     V: Debug,
 {
     #[inline]
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         let key_provider = K::static_container().key_by_offset_provider();
         let mut debug_map = f.debug_map();
         for (key, value) in self.clone() {
@@ -518,14 +750,14 @@ where
     }
 }
 
-impl<'a, K, V> Debug for IterMut<'a, K, V>
+impl<K, V> Debug for IterMut<'_, K, V>
 where
     K: BlazeMapIdStatic,
     K::OrigType: Debug,
     V: Debug,
 {
     #[inline]
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         let Self {
             inner,
             current_position,
@@ -542,7 +774,16 @@ where
     }
 }
 
-impl<'a, K, V> Clone for Keys<'a, K, V> {
+impl<K, V> Clone for Keys<'_, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<K, V> Clone for Values<'_, K, V> {
     #[inline]
     fn clone(&self) -> Self {
         Self {
@@ -551,7 +792,43 @@ impl<'a, K, V> Clone for Keys<'a, K, V> {
     }
 }
 
-impl<'a, K, V> Clone for Values<'a, K, V> {
+impl<K, V> Default for Iter<'_, K, V> {
+    /// Creates an empty `Iter` that yields nothing.
+    #[inline]
+    fn default() -> Self {
+        Self {
+            inner: NonNull::dangling().as_ptr(),
+            current_position: 0,
+            len: 0,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<K, V> Default for Keys<'_, K, V> {
+    /// Creates an empty `Keys` that yields nothing.
+    #[inline]
+    fn default() -> Self {
+        Self {
+            inner: Iter::default(),
+        }
+    }
+}
+
+impl<K, V> Default for Values<'_, K, V> {
+    /// Creates an empty `Values` that yields nothing.
+    #[inline]
+    fn default() -> Self {
+        Self {
+            inner: Iter::default(),
+        }
+    }
+}
+
+impl<K, V> Clone for IntoIter<K, V>
+where
+    V: Clone,
+{
     #[inline]
     fn clone(&self) -> Self {
         Self {
@@ -560,13 +837,37 @@ impl<'a, K, V> Clone for Values<'a, K, V> {
     }
 }
 
-impl<'a, K, V> Debug for Keys<'a, K, V>
+impl<K, V> Clone for IntoKeys<K, V>
+where
+    V: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<K, V> Clone for IntoValues<K, V>
+where
+    V: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<K, V> Debug for Keys<'_, K, V>
 where
     K: BlazeMapIdStatic,
     K::OrigType: Debug,
 {
     #[inline]
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         let key_provider = K::static_container().key_by_offset_provider();
         let mut debug_list = f.debug_list();
         for key in self.clone() {
@@ -577,22 +878,22 @@ where
     }
 }
 
-impl<'a, K, V> Debug for Values<'a, K, V>
+impl<K, V> Debug for Values<'_, K, V>
 where
     V: Debug,
 {
     #[inline]
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         f.debug_list().entries(self.clone()).finish()
     }
 }
 
-impl<'a, K, V> Debug for ValuesMut<'a, K, V>
+impl<K, V> Debug for ValuesMut<'_, K, V>
 where
     V: Debug,
 {
     #[inline]
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         let IterMut {
             inner,
             current_position,
@@ -611,6 +912,18 @@ where
     }
 }
 
+impl<K, V> Debug for ValuesMutChunk<'_, K, V>
+where
+    V: Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_list()
+            .entries(self.inner.as_slice().iter().filter_map(Option::as_ref))
+            .finish()
+    }
+}
+
 impl<K, V> Debug for IntoIter<K, V>
 where
     K: BlazeMapIdStatic,
@@ -618,7 +931,7 @@ where
     V: Debug,
 {
     #[inline]
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         self.inner.fmt(f)
     }
 }
@@ -629,7 +942,7 @@ where
     K::OrigType: Debug,
 {
     #[inline]
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         self.inner.inner.keys().fmt(f)
     }
 }
@@ -640,19 +953,77 @@ where
     V: Debug,
 {
     #[inline]
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         self.inner.inner.values().fmt(f)
     }
 }
 
-impl<'a, K, V> Debug for Drain<'a, K, V>
+impl<K, V> Debug for Drain<'_, K, V>
 where
     K: BlazeMapIdStatic,
     K::OrigType: Debug,
     V: Debug,
 {
     #[inline]
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         self.map.fmt(f)
     }
 }
+
+impl<K, V> Debug for DrainRange<'_, K, V>
+where
+    K: BlazeMapIdStatic,
+    K::OrigType: Debug,
+    V: Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let slots = &self.map.inner[self.current_position..self.end_position];
+        fmt_slots::<K, V>(slots, self.current_position, f)
+    }
+}
+
+impl<K, V, F> Debug for ExtractIf<'_, K, V, F>
+where
+    K: BlazeMapIdStatic,
+    K::OrigType: Debug,
+    V: Debug,
+    F: FnMut(K, &mut V) -> bool,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let slots = self
+            .map
+            .inner
+            .get(self.current_position..)
+            .unwrap_or_default();
+        fmt_slots::<K, V>(slots, self.current_position, f)
+    }
+}
+
+/// Formats the occupied `slots` as a map from the original keys to the values,
+/// given that the first slot corresponds to `first_offset`.
+///
+/// The original keys are resolved under a single read guard on the `K`
+/// registry.
+#[inline]
+fn fmt_slots<K, V>(
+    slots: &[Option<V>],
+    first_offset: usize,
+    f: &mut Formatter<'_>,
+) -> core::fmt::Result
+where
+    K: BlazeMapIdStatic,
+    K::OrigType: Debug,
+    V: Debug,
+{
+    let guard = K::static_container().key_by_offset_provider();
+    let mut debug_map = f.debug_map();
+    for (offset, value) in (first_offset..).zip(slots) {
+        if let Some(value) = value {
+            let key = unsafe { guard.key_by_offset_unchecked(offset) };
+            debug_map.entry(key.borrow(), value);
+        }
+    }
+    debug_map.finish()
+}
@@ -0,0 +1,235 @@
+use crate::{
+    collections::map::{BlazeMap, Iter},
+    prelude::{BlazeMapId, BlazeMapIdStatic},
+};
+use core::{
+    fmt::{Debug, Formatter},
+    marker::PhantomData,
+};
+use rayon::iter::{
+    plumbing::UnindexedConsumer, IndexedParallelIterator, IntoParallelIterator,
+    IntoParallelRefIterator, IntoParallelRefMutIterator, ParallelIterator,
+};
+
+/// A parallel iterator over the entries of a [`BlazeMap`].
+///
+/// This `struct` is created by the [`into_par_iter`] method on [`&BlazeMap`]
+/// (provided by the [`IntoParallelIterator`] trait). See its documentation for
+/// more.
+///
+/// [`into_par_iter`]: IntoParallelIterator::into_par_iter
+/// [`&BlazeMap`]: BlazeMap
+pub struct ParIter<'a, K, V> {
+    pub(in crate::collections::map) inner: &'a [Option<V>],
+
+    pub(in crate::collections::map) len: usize,
+
+    pub(in crate::collections::map) phantom: PhantomData<K>,
+}
+
+/// A mutable parallel iterator over the entries of a [`BlazeMap`].
+///
+/// This `struct` is created by the [`into_par_iter`] method on
+/// [`&mut BlazeMap`] (provided by the [`IntoParallelIterator`] trait).
+/// See its documentation for more.
+///
+/// [`into_par_iter`]: IntoParallelIterator::into_par_iter
+/// [`&mut BlazeMap`]: BlazeMap
+pub struct ParIterMut<'a, K, V> {
+    pub(in crate::collections::map) inner: &'a mut [Option<V>],
+
+    pub(in crate::collections::map) len: usize,
+
+    pub(in crate::collections::map) phantom: PhantomData<K>,
+}
+
+/// An owning parallel iterator over the entries of a [`BlazeMap`].
+///
+/// This `struct` is created by the [`into_par_iter`] method on [`BlazeMap`]
+/// (provided by the [`IntoParallelIterator`] trait). See its documentation for
+/// more.
+///
+/// [`into_par_iter`]: IntoParallelIterator::into_par_iter
+pub struct IntoParIter<K, V> {
+    pub(in crate::collections::map) inner: BlazeMap<K, V>,
+}
+
+impl<'a, K, V> ParallelIterator for ParIter<'a, K, V>
+where
+    K: BlazeMapId + Send,
+    V: Sync,
+{
+    type Item = (K, &'a V);
+
+    #[inline]
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        self.inner
+            .par_iter()
+            .enumerate()
+            .filter_map(|(offset, value)| {
+                let value = value.as_ref()?;
+                Some((unsafe { K::from_offset_unchecked(offset) }, value))
+            })
+            .drive_unindexed(consumer)
+    }
+}
+
+impl<'a, K, V> ParallelIterator for ParIterMut<'a, K, V>
+where
+    K: BlazeMapId + Send,
+    V: Send,
+{
+    type Item = (K, &'a mut V);
+
+    #[inline]
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        self.inner
+            .par_iter_mut()
+            .enumerate()
+            .filter_map(|(offset, value)| {
+                let value = value.as_mut()?;
+                Some((unsafe { K::from_offset_unchecked(offset) }, value))
+            })
+            .drive_unindexed(consumer)
+    }
+}
+
+impl<K, V> ParallelIterator for IntoParIter<K, V>
+where
+    K: BlazeMapId + Send,
+    V: Send,
+{
+    type Item = (K, V);
+
+    #[inline]
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        self.inner
+            .inner
+            .into_par_iter()
+            .enumerate()
+            .filter_map(|(offset, value)| {
+                let value = value?;
+                Some((unsafe { K::from_offset_unchecked(offset) }, value))
+            })
+            .drive_unindexed(consumer)
+    }
+}
+
+impl<K, V> IntoParallelIterator for BlazeMap<K, V>
+where
+    K: BlazeMapId + Send,
+    V: Send,
+{
+    type Iter = IntoParIter<K, V>;
+    type Item = (K, V);
+
+    #[inline]
+    fn into_par_iter(self) -> IntoParIter<K, V> {
+        debug_assert_eq!(
+            self.inner.iter().filter_map(Option::as_ref).count(),
+            self.len
+        );
+        IntoParIter { inner: self }
+    }
+}
+
+impl<'a, K, V> IntoParallelIterator for &'a BlazeMap<K, V>
+where
+    K: BlazeMapId + Send,
+    V: Sync,
+{
+    type Iter = ParIter<'a, K, V>;
+    type Item = (K, &'a V);
+
+    #[inline]
+    fn into_par_iter(self) -> ParIter<'a, K, V> {
+        debug_assert_eq!(
+            self.inner.iter().filter_map(Option::as_ref).count(),
+            self.len
+        );
+        ParIter {
+            inner: &self.inner,
+            len: self.len,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, K, V> IntoParallelIterator for &'a mut BlazeMap<K, V>
+where
+    K: BlazeMapId + Send,
+    V: Send,
+{
+    type Iter = ParIterMut<'a, K, V>;
+    type Item = (K, &'a mut V);
+
+    #[inline]
+    fn into_par_iter(self) -> ParIterMut<'a, K, V> {
+        debug_assert_eq!(
+            self.inner.iter().filter_map(Option::as_ref).count(),
+            self.len
+        );
+        ParIterMut {
+            inner: &mut self.inner,
+            len: self.len,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<K, V> Debug for ParIter<'_, K, V>
+where
+    K: BlazeMapIdStatic,
+    K::OrigType: Debug,
+    V: Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let iter = Iter::<K, V> {
+            inner: self.inner.as_ptr(),
+            current_position: 0,
+            len: self.len,
+            phantom: PhantomData,
+        };
+        iter.fmt(f)
+    }
+}
+
+impl<K, V> Debug for ParIterMut<'_, K, V>
+where
+    K: BlazeMapIdStatic,
+    K::OrigType: Debug,
+    V: Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let iter = Iter::<K, V> {
+            inner: self.inner.as_ptr(),
+            current_position: 0,
+            len: self.len,
+            phantom: PhantomData,
+        };
+        iter.fmt(f)
+    }
+}
+
+impl<K, V> Debug for IntoParIter<K, V>
+where
+    K: BlazeMapIdStatic,
+    K::OrigType: Debug,
+    V: Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.inner.fmt(f)
+    }
+}
@@ -0,0 +1,962 @@
+#![allow(clippy::module_name_repetitions)]
+
+pub use crate::collections::set::iters::{IntoIter, Iter};
+use crate::{
+    collections::map::BlazeMap,
+    traits::{BlazeMapId, BlazeMapIdStatic, KeyByOffsetProvider, TypeInfoContainer},
+};
+use alloc::{vec, vec::Vec};
+use core::{
+    borrow::Borrow,
+    fmt::{Debug, Display, Formatter},
+    marker::PhantomData,
+    ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Sub, SubAssign},
+};
+
+mod iters;
+
+/// A bitmask-based analogue of a [`HashSet`](std::collections::HashSet).
+pub struct BlazeSet<K> {
+    pub(in crate::collections) bitmask: Vec<u8>,
+    pub(in crate::collections::set) len: usize,
+    phantom: PhantomData<K>,
+}
+
+impl<K> BlazeSet<K> {
+    /// Creates a new instance of [`BlazeSet`].
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            bitmask: vec![],
+            len: 0,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns the number of elements in the set.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the set contains no elements.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Clears the set, removing all elements. Keeps the allocated memory for
+    /// reuse.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.bitmask.clear();
+        self.len = 0;
+    }
+
+    /// Reserves capacity for at least `additional_offset` more offsets
+    /// beyond the ones currently covered by the set.
+    ///
+    /// The set may reserve more space to speculatively avoid frequent
+    /// reallocations.
+    #[inline]
+    pub fn reserve(&mut self, additional_offset: usize) {
+        self.bitmask.reserve(additional_offset.div_ceil(8));
+    }
+
+    /// Grows the set so that it covers all the offsets
+    /// that the given [`BlazeMap`] currently has room for.
+    ///
+    /// Useful for keeping a companion set sized consistently with the map
+    /// over the same key space.
+    #[inline]
+    pub fn reserve_like<V>(&mut self, map: &BlazeMap<K, V>) {
+        let required_len = map.inner.len().div_ceil(8);
+        if self.bitmask.len() < required_len {
+            self.bitmask.resize(required_len, 0);
+        }
+    }
+
+    /// Adds all the keys of `other` to the set.
+    ///
+    /// Unlike [`Extend`], this merges the bitmasks in a single pass
+    /// instead of inserting the keys one by one.
+    #[inline]
+    pub fn extend_from_set(&mut self, other: &Self) {
+        self.union_in_place(other);
+    }
+
+    /// Returns a new set containing the keys that are in `self`, in `other`,
+    /// or in both.
+    #[inline]
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let (shorter, longer) = Self::by_bitmask_len(self, other);
+        let mut bitmask = longer.bitmask.clone();
+        bitmask
+            .iter_mut()
+            .zip(&shorter.bitmask)
+            .for_each(|(lhs, rhs)| *lhs |= rhs);
+        Self::from_bitmask(bitmask)
+    }
+
+    /// Returns a new set containing the keys that are both in `self` and in
+    /// `other`.
+    #[inline]
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let bitmask = self
+            .bitmask
+            .iter()
+            .zip(&other.bitmask)
+            .map(|(lhs, rhs)| lhs & rhs)
+            .collect();
+        Self::from_bitmask(bitmask)
+    }
+
+    /// Returns a new set containing the keys that are in `self` but not in
+    /// `other`.
+    #[inline]
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut bitmask = self.bitmask.clone();
+        bitmask
+            .iter_mut()
+            .zip(&other.bitmask)
+            .for_each(|(lhs, rhs)| *lhs &= !rhs);
+        Self::from_bitmask(bitmask)
+    }
+
+    /// Returns a new set containing the keys that are in `self` or in `other`,
+    /// but not in both.
+    #[inline]
+    #[must_use]
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        let (shorter, longer) = Self::by_bitmask_len(self, other);
+        let mut bitmask = longer.bitmask.clone();
+        bitmask
+            .iter_mut()
+            .zip(&shorter.bitmask)
+            .for_each(|(lhs, rhs)| *lhs ^= rhs);
+        Self::from_bitmask(bitmask)
+    }
+
+    /// Returns `true` if every key of `self` is also contained in `other`.
+    #[inline]
+    #[must_use]
+    pub fn is_subset(&self, other: &Self) -> bool {
+        let (overlapping, rest) = self
+            .bitmask
+            .split_at(self.bitmask.len().min(other.bitmask.len()));
+        overlapping
+            .iter()
+            .zip(&other.bitmask)
+            .all(|(lhs, rhs)| lhs & !rhs == 0)
+            && rest.iter().all(|byte| *byte == 0)
+    }
+
+    /// Returns `true` if every key of `other` is also contained in `self`.
+    #[inline]
+    #[must_use]
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Returns `true` if `self` and `other` have no keys in common.
+    #[inline]
+    #[must_use]
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.bitmask
+            .iter()
+            .zip(&other.bitmask)
+            .all(|(lhs, rhs)| lhs & rhs == 0)
+    }
+
+    /// Adds all the keys of `other` to `self`.
+    #[inline]
+    fn union_in_place(&mut self, other: &Self) {
+        if self.bitmask.len() < other.bitmask.len() {
+            self.bitmask.resize(other.bitmask.len(), 0);
+        }
+        self.bitmask
+            .iter_mut()
+            .zip(&other.bitmask)
+            .for_each(|(lhs, rhs)| *lhs |= rhs);
+        self.recompute_len();
+    }
+
+    /// Retains only the keys of `self` that are also contained in `other`.
+    #[inline]
+    fn intersection_in_place(&mut self, other: &Self) {
+        self.bitmask.truncate(other.bitmask.len());
+        self.bitmask
+            .iter_mut()
+            .zip(&other.bitmask)
+            .for_each(|(lhs, rhs)| *lhs &= rhs);
+        self.recompute_len();
+    }
+
+    /// Removes all the keys of `other` from `self`.
+    #[inline]
+    fn difference_in_place(&mut self, other: &Self) {
+        self.bitmask
+            .iter_mut()
+            .zip(&other.bitmask)
+            .for_each(|(lhs, rhs)| *lhs &= !rhs);
+        self.recompute_len();
+    }
+
+    /// Replaces `self` with the keys that are in `self` or in `other`,
+    /// but not in both.
+    #[inline]
+    fn symmetric_difference_in_place(&mut self, other: &Self) {
+        if self.bitmask.len() < other.bitmask.len() {
+            self.bitmask.resize(other.bitmask.len(), 0);
+        }
+        self.bitmask
+            .iter_mut()
+            .zip(&other.bitmask)
+            .for_each(|(lhs, rhs)| *lhs ^= rhs);
+        self.recompute_len();
+    }
+
+    /// Recomputes `len` from the current state of `bitmask`.
+    #[inline]
+    fn recompute_len(&mut self) {
+        self.len = count_ones(&self.bitmask);
+    }
+
+    /// Creates a new instance of [`BlazeSet`] from the given bitmask.
+    #[inline]
+    fn from_bitmask(bitmask: Vec<u8>) -> Self {
+        let len = count_ones(&bitmask);
+        Self {
+            bitmask,
+            len,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns the given sets ordered by the length of their bitmasks.
+    #[inline]
+    fn by_bitmask_len<'a>(lhs: &'a Self, rhs: &'a Self) -> (&'a Self, &'a Self) {
+        if lhs.bitmask.len() <= rhs.bitmask.len() {
+            (lhs, rhs)
+        } else {
+            (rhs, lhs)
+        }
+    }
+}
+
+impl<K> BlazeSet<K>
+where
+    K: BlazeMapId,
+{
+    /// An iterator visiting all elements in ascending offset order.
+    /// The iterator element type is `K`.
+    #[inline]
+    #[must_use]
+    pub fn iter(&self) -> Iter<'_, K> {
+        Iter {
+            bitmask: &self.bitmask,
+            current_position: 0,
+            len: self.len,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns `true` if the set contains the specified key.
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, key: K) -> bool {
+        let offset = key.get_offset();
+        self.bitmask
+            .get(offset / 8)
+            .is_some_and(|byte| byte & (1 << (offset % 8)) != 0)
+    }
+
+    /// Adds a key to the set.
+    ///
+    /// Returns whether the key was newly inserted.
+    #[inline]
+    pub fn insert(&mut self, key: K) -> bool {
+        let offset = key.get_offset();
+        let position = offset / 8;
+        let bit = 1 << (offset % 8);
+        if position >= self.bitmask.len() {
+            // `Vec::resize` grows the allocation with amortized headroom.
+            // `position + 1` can't overflow, since `position <= usize::MAX / 8`.
+            self.bitmask.resize(position + 1, 0);
+        }
+        let byte = unsafe { self.bitmask.get_unchecked_mut(position) };
+        if *byte & bit != 0 {
+            return false;
+        }
+        *byte |= bit;
+        self.len += 1;
+        debug_assert_eq!(count_ones(&self.bitmask), self.len);
+        true
+    }
+
+    /// Removes a key from the set.
+    ///
+    /// Returns whether the key was present in the set.
+    #[inline]
+    pub fn remove(&mut self, key: K) -> bool {
+        let offset = key.get_offset();
+        let bit = 1 << (offset % 8);
+        match self.bitmask.get_mut(offset / 8) {
+            Some(byte) if *byte & bit != 0 => {
+                *byte &= !bit;
+                self.len -= 1;
+                debug_assert_eq!(count_ones(&self.bitmask), self.len);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Retains only the keys specified by the predicate.
+    ///
+    /// In other words, removes all keys `k` for which `f(k)` returns `false`.
+    /// The keys are visited in ascending offset order.
+    #[inline]
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(K) -> bool,
+    {
+        for (position, byte) in self.bitmask.iter_mut().enumerate() {
+            let mut remaining = *byte;
+            while remaining != 0 {
+                let bit_index = remaining.trailing_zeros() as usize;
+                let bit = 1 << bit_index;
+                remaining &= !bit;
+                let key = unsafe { K::from_offset_unchecked(position * 8 + bit_index) };
+                if !f(key) {
+                    *byte &= !bit;
+                    self.len -= 1;
+                }
+            }
+        }
+        debug_assert_eq!(count_ones(&self.bitmask), self.len);
+    }
+
+    /// Converts the set into a [`BlazeMap`], computing the value of every key
+    /// with `f`.
+    ///
+    /// The keys are visited in ascending offset order.
+    #[inline]
+    #[must_use]
+    pub fn into_map_with<V, F>(self, mut f: F) -> BlazeMap<K, V>
+    where
+        F: FnMut(K) -> V,
+    {
+        let mut map = BlazeMap::new();
+        map.inner.resize_with(self.bitmask.len() * 8, || None);
+        map.len = self.len;
+        for key in self {
+            unsafe { *map.inner.get_unchecked_mut(key.get_offset()) = Some(f(key)) };
+        }
+        map
+    }
+}
+
+/// Returns the number of set bits in the given bitmask.
+#[inline]
+fn count_ones(bitmask: &[u8]) -> usize {
+    bitmask.iter().map(|byte| byte.count_ones() as usize).sum()
+}
+
+impl<K> Clone for BlazeSet<K> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            bitmask: self.bitmask.clone(),
+            len: self.len,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<K> PartialEq for BlazeSet<K> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        if self.len != other.len {
+            return false;
+        }
+        let (shorter, longer) = Self::by_bitmask_len(self, other);
+        let (head, tail) = longer.bitmask.split_at(shorter.bitmask.len());
+        shorter.bitmask == head && tail.iter().all(|byte| *byte == 0)
+    }
+}
+
+impl<K> Eq for BlazeSet<K> {}
+
+impl<K> Default for BlazeSet<K> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K> IntoIterator for BlazeSet<K>
+where
+    K: BlazeMapId,
+{
+    type Item = K;
+    type IntoIter = IntoIter<K>;
+
+    #[inline]
+    fn into_iter(self) -> IntoIter<K> {
+        IntoIter {
+            inner: self,
+            current_position: 0,
+        }
+    }
+}
+
+impl<'a, K> IntoIterator for &'a BlazeSet<K>
+where
+    K: BlazeMapId,
+{
+    type Item = K;
+    type IntoIter = Iter<'a, K>;
+
+    #[inline]
+    fn into_iter(self) -> Iter<'a, K> {
+        self.iter()
+    }
+}
+
+impl<K, V> From<&BlazeMap<K, V>> for BlazeSet<K>
+where
+    K: BlazeMapId,
+{
+    #[inline]
+    fn from(map: &BlazeMap<K, V>) -> Self {
+        let mut bitmask = vec![0; map.inner.len().div_ceil(8)];
+        for (offset, _) in map
+            .inner
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| value.is_some())
+        {
+            unsafe { *bitmask.get_unchecked_mut(offset / 8) |= 1 << (offset % 8) };
+        }
+        Self {
+            bitmask,
+            len: map.len,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<K> Extend<K> for BlazeSet<K>
+where
+    K: BlazeMapId,
+{
+    #[inline]
+    fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        for key in iter {
+            self.insert(key);
+        }
+    }
+}
+
+impl<'a, K> Extend<&'a K> for BlazeSet<K>
+where
+    K: BlazeMapId,
+{
+    #[inline]
+    fn extend<I: IntoIterator<Item = &'a K>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<K> Debug for BlazeSet<K>
+where
+    K: BlazeMapIdStatic,
+    <K as BlazeMapId>::OrigType: Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let guard = K::static_container().key_by_offset_provider();
+        let mut debug_set = f.debug_set();
+        for key in self {
+            let key = unsafe { guard.key_by_offset_unchecked(key.get_offset()) };
+            debug_set.entry(key.borrow());
+        }
+        debug_set.finish()
+    }
+}
+
+impl<K> Display for BlazeSet<K>
+where
+    K: BlazeMapIdStatic,
+    <K as BlazeMapId>::OrigType: Display,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let guard = K::static_container().key_by_offset_provider();
+        write!(f, "{{")?;
+        for (idx, key) in self.iter().enumerate() {
+            let key = unsafe { guard.key_by_offset_unchecked(key.get_offset()) };
+            if idx == 0 {
+                write!(f, "{}", key.borrow())?;
+            } else {
+                write!(f, ", {}", key.borrow())?;
+            }
+        }
+        write!(f, "}}")
+    }
+}
+
+macro_rules! impl_set_op {
+    ($op_trait:ident, $op:ident, $assign_trait:ident, $assign:ident, $method:ident, $in_place:ident) => {
+        impl<K> $op_trait for &BlazeSet<K> {
+            type Output = BlazeSet<K>;
+
+            #[inline]
+            fn $op(self, rhs: Self) -> BlazeSet<K> {
+                self.$method(rhs)
+            }
+        }
+
+        impl<K> $op_trait for BlazeSet<K> {
+            type Output = Self;
+
+            #[inline]
+            fn $op(mut self, rhs: Self) -> Self {
+                self.$in_place(&rhs);
+                self
+            }
+        }
+
+        impl<K> $assign_trait<&Self> for BlazeSet<K> {
+            #[inline]
+            fn $assign(&mut self, rhs: &Self) {
+                self.$in_place(rhs);
+            }
+        }
+
+        impl<K> $assign_trait for BlazeSet<K> {
+            #[inline]
+            fn $assign(&mut self, rhs: Self) {
+                self.$in_place(&rhs);
+            }
+        }
+    };
+}
+
+impl_set_op!(
+    BitOr,
+    bitor,
+    BitOrAssign,
+    bitor_assign,
+    union,
+    union_in_place
+);
+impl_set_op!(
+    BitAnd,
+    bitand,
+    BitAndAssign,
+    bitand_assign,
+    intersection,
+    intersection_in_place
+);
+impl_set_op!(
+    BitXor,
+    bitxor,
+    BitXorAssign,
+    bitxor_assign,
+    symmetric_difference,
+    symmetric_difference_in_place
+);
+impl_set_op!(
+    Sub,
+    sub,
+    SubAssign,
+    sub_assign,
+    difference,
+    difference_in_place
+);
+
+#[cfg(all(test, not(feature = "loom")))]
+mod tests {
+    use crate::{
+        collections::{map::BlazeMap, set::BlazeSet},
+        define_key_wrapper, define_plain_id,
+        prelude::BlazeMapId,
+    };
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use std::collections::HashSet;
+
+    #[allow(dead_code)]
+    mod id {
+        super::define_plain_id! {
+            pub(super) struct Id
+        }
+    }
+    use id::Id;
+
+    fn id(offset: usize) -> Id {
+        unsafe { Id::from_offset_unchecked(offset) }
+    }
+
+    #[test]
+    fn iter() {
+        let offsets = [0, 3, 7, 8, 15, 16, 42, 63, 64, 100];
+
+        let mut set = BlazeSet::new();
+        for offset in offsets.iter().rev() {
+            assert!(set.insert(id(*offset)));
+        }
+        assert!(!set.insert(id(42)));
+        assert_eq!(set.len(), offsets.len());
+
+        let iter = set.iter();
+        assert_eq!(iter.len(), offsets.len());
+        let collected: Vec<_> = iter.map(BlazeMapId::get_offset).collect();
+        assert_eq!(collected, offsets);
+
+        assert!(set.remove(id(42)));
+        assert!(!set.remove(id(42)));
+        assert!(!set.remove(id(1000)));
+        let collected: Vec<_> = set.iter().map(BlazeMapId::get_offset).collect();
+        assert_eq!(collected, [0, 3, 7, 8, 15, 16, 63, 64, 100]);
+    }
+
+    #[test]
+    fn insert_grow() {
+        let mut set = BlazeSet::new();
+        assert!(set.insert(id(0)));
+        assert_eq!(set.bitmask, [0b1]);
+        assert_eq!(set.len(), 1);
+
+        let mut set = BlazeSet::new();
+        assert!(set.insert(id(100)));
+        assert_eq!(set.bitmask.len(), 100 / 8 + 1);
+        assert!(set.bitmask[..100 / 8].iter().all(|byte| *byte == 0));
+        assert_eq!(set.bitmask[100 / 8], 1 << (100 % 8));
+        assert_eq!(set.len(), 1);
+
+        assert!(!set.insert(id(100)));
+        assert_eq!(set.len(), 1);
+
+        assert!(set.insert(id(9)));
+        assert_eq!(set.bitmask.len(), 100 / 8 + 1);
+        assert_eq!(set.bitmask[1], 1 << 1);
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(id(9)));
+        assert!(set.contains(id(100)));
+        assert!(!set.contains(id(8)));
+        assert!(!set.contains(id(101)));
+    }
+
+    #[test]
+    fn into_iter() {
+        let offsets = [1, 2, 9, 31, 32, 77];
+
+        let mut set = BlazeSet::new();
+        for offset in offsets {
+            set.insert(id(offset));
+        }
+        let mut iter = set.clone().into_iter();
+        assert_eq!(iter.len(), offsets.len());
+        assert_eq!(iter.next().map(BlazeMapId::get_offset), Some(1));
+        assert_eq!(iter.len(), offsets.len() - 1);
+        assert_eq!(
+            iter.map(BlazeMapId::get_offset).collect::<Vec<_>>(),
+            offsets[1..]
+        );
+
+        let collected: Vec<_> = set.into_iter().map(BlazeMapId::get_offset).collect();
+        assert_eq!(collected, offsets);
+        assert_eq!(BlazeSet::<Id>::new().into_iter().next(), None);
+    }
+
+    fn random_sets(seed: u64) -> impl Iterator<Item = (BlazeSet<Id>, HashSet<usize>)> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..20).map(move |_| {
+            let max_offset = rng.gen_range(1..200);
+            let num_offsets = rng.gen_range(0..max_offset);
+            let mut set = BlazeSet::new();
+            let mut hash_set = HashSet::new();
+            for _ in 0..num_offsets {
+                let offset = rng.gen_range(0..max_offset);
+                assert_eq!(set.insert(id(offset)), hash_set.insert(offset));
+            }
+            (set, hash_set)
+        })
+    }
+
+    fn to_hash_set(set: &BlazeSet<Id>) -> HashSet<usize> {
+        assert_eq!(set.len(), set.iter().count());
+        set.iter().map(BlazeMapId::get_offset).collect()
+    }
+
+    #[test]
+    fn set_algebra() {
+        let empty = (BlazeSet::new(), HashSet::new());
+        let lhs_sets = random_sets(0).chain(core::iter::once(empty.clone()));
+        for (lhs, lhs_hash) in lhs_sets {
+            let rhs_sets = random_sets(1).chain(core::iter::once(empty.clone()));
+            for (rhs, rhs_hash) in rhs_sets {
+                assert_eq!(to_hash_set(&lhs.union(&rhs)), &lhs_hash | &rhs_hash);
+                assert_eq!(to_hash_set(&lhs.intersection(&rhs)), &lhs_hash & &rhs_hash);
+                assert_eq!(to_hash_set(&lhs.difference(&rhs)), &lhs_hash - &rhs_hash);
+                assert_eq!(
+                    to_hash_set(&lhs.symmetric_difference(&rhs)),
+                    &lhs_hash ^ &rhs_hash
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn set_operators() {
+        let empty = (BlazeSet::new(), HashSet::new());
+        let lhs_sets = random_sets(0).chain(core::iter::once(empty.clone()));
+        for (lhs, lhs_hash) in lhs_sets {
+            let rhs_sets = random_sets(1).chain(core::iter::once(empty.clone()));
+            for (rhs, rhs_hash) in rhs_sets {
+                assert_eq!(&lhs | &rhs, lhs.union(&rhs));
+                assert_eq!(&lhs & &rhs, lhs.intersection(&rhs));
+                assert_eq!(&lhs ^ &rhs, lhs.symmetric_difference(&rhs));
+                assert_eq!(&lhs - &rhs, lhs.difference(&rhs));
+
+                assert_eq!(lhs.clone() | rhs.clone(), lhs.union(&rhs));
+                assert_eq!(lhs.clone() & rhs.clone(), lhs.intersection(&rhs));
+                assert_eq!(lhs.clone() ^ rhs.clone(), lhs.symmetric_difference(&rhs));
+                assert_eq!(lhs.clone() - rhs.clone(), lhs.difference(&rhs));
+
+                let mut set = lhs.clone();
+                set |= &rhs;
+                assert_eq!(to_hash_set(&set), &lhs_hash | &rhs_hash);
+                let mut set = lhs.clone();
+                set &= &rhs;
+                assert_eq!(to_hash_set(&set), &lhs_hash & &rhs_hash);
+                let mut set = lhs.clone();
+                set ^= &rhs;
+                assert_eq!(to_hash_set(&set), &lhs_hash ^ &rhs_hash);
+                let mut set = lhs.clone();
+                set -= &rhs;
+                assert_eq!(to_hash_set(&set), &lhs_hash - &rhs_hash);
+
+                let mut set = lhs.clone();
+                set |= rhs.clone();
+                set -= rhs.clone();
+                set ^= rhs.clone();
+                set &= rhs.clone();
+                assert_eq!(to_hash_set(&set), rhs_hash.clone());
+            }
+        }
+    }
+
+    #[test]
+    fn set_predicates() {
+        let empty = BlazeSet::<Id>::new();
+        assert!(empty.is_subset(&empty));
+        assert!(empty.is_superset(&empty));
+        assert!(empty.is_disjoint(&empty));
+
+        let lhs_sets = random_sets(0).chain(core::iter::once((empty.clone(), HashSet::new())));
+        for (lhs, lhs_hash) in lhs_sets {
+            assert!(lhs.is_subset(&lhs));
+            assert!(lhs.is_superset(&lhs));
+            assert_eq!(lhs.is_disjoint(&lhs), lhs.is_empty());
+            assert!(empty.is_subset(&lhs));
+            assert!(lhs.is_superset(&empty));
+            assert!(lhs.is_disjoint(&empty));
+
+            let rhs_sets = random_sets(1).chain(core::iter::once((empty.clone(), HashSet::new())));
+            for (rhs, rhs_hash) in rhs_sets {
+                assert_eq!(lhs.is_subset(&rhs), lhs_hash.is_subset(&rhs_hash));
+                assert_eq!(lhs.is_superset(&rhs), lhs_hash.is_superset(&rhs_hash));
+                assert_eq!(lhs.is_disjoint(&rhs), lhs_hash.is_disjoint(&rhs_hash));
+            }
+        }
+
+        let mut short = BlazeSet::new();
+        short.insert(id(1));
+        let mut long = short.clone();
+        long.insert(id(100));
+        long.remove(id(100));
+        assert!(long.is_subset(&short));
+        assert!(short.is_superset(&long));
+        long.insert(id(100));
+        assert!(!long.is_subset(&short));
+        assert!(short.is_subset(&long));
+        assert!(!short.is_disjoint(&long));
+        long.remove(id(1));
+        assert!(short.is_disjoint(&long));
+    }
+
+    #[test]
+    fn display() {
+        define_key_wrapper! {
+            struct Key(&'static str)
+        }
+
+        let mut set = BlazeSet::new();
+        assert_eq!(set.to_string(), "{}");
+
+        let first = Key::new("first");
+        let second = Key::new("second");
+        let third = Key::new("third");
+        set.insert(third);
+        assert_eq!(set.to_string(), "{third}");
+        set.insert(first);
+        set.insert(second);
+        assert_eq!(set.to_string(), "{first, second, third}");
+    }
+
+    #[test]
+    fn extend() {
+        let mut set = BlazeSet::new();
+        set.extend([3, 42, 3, 7].map(id));
+        assert_eq!(set.len(), 3);
+        set.extend(&[id(7), id(100)]);
+        assert_eq!(
+            set.iter().map(BlazeMapId::get_offset).collect::<Vec<_>>(),
+            [3, 7, 42, 100]
+        );
+    }
+
+    #[test]
+    fn extend_from_set() {
+        for (lhs, lhs_hash) in random_sets(0) {
+            for (rhs, rhs_hash) in random_sets(1) {
+                let mut set = lhs.clone();
+                set.extend_from_set(&rhs);
+                assert_eq!(set.len(), (&lhs_hash | &rhs_hash).len());
+                assert_eq!(to_hash_set(&set), &lhs_hash | &rhs_hash);
+                assert_eq!(set, lhs.union(&rhs));
+            }
+        }
+
+        let mut set = BlazeSet::new();
+        set.insert(id(1));
+        set.extend_from_set(&BlazeSet::new());
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn retain() {
+        let mut set = BlazeSet::new();
+        set.extend((0..50).map(id));
+        set.insert(id(100));
+        set.retain(|key| key.get_offset() % 2 == 0);
+        assert_eq!(set.len(), 26);
+        assert!((0..50).all(|offset| set.contains(id(offset)) == (offset % 2 == 0)));
+        assert!(set.contains(id(100)));
+
+        let mut visited = Vec::new();
+        set.retain(|key| {
+            visited.push(key.get_offset());
+            false
+        });
+        assert!(visited.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(visited.len(), 26);
+        assert!(set.is_empty());
+        assert_eq!(set.iter().next(), None);
+    }
+
+    #[test]
+    fn len_matches_count_ones() {
+        let bits = |set: &BlazeSet<Id>| {
+            set.bitmask
+                .iter()
+                .map(|byte| byte.count_ones() as usize)
+                .sum::<usize>()
+        };
+        for (lhs, _) in random_sets(0) {
+            for (rhs, _) in random_sets(1) {
+                for set in [
+                    lhs.union(&rhs),
+                    lhs.intersection(&rhs),
+                    lhs.difference(&rhs),
+                    lhs.symmetric_difference(&rhs),
+                    lhs.clone() | rhs.clone(),
+                    lhs.clone() & rhs.clone(),
+                    lhs.clone() ^ rhs.clone(),
+                    lhs.clone() - rhs.clone(),
+                ] {
+                    assert_eq!(set.len(), bits(&set));
+                    assert_eq!(set.iter().count(), set.len());
+                }
+            }
+        }
+
+        let mut set = BlazeSet::new();
+        for offset in [0, 7, 8, 15, 16, 63] {
+            set.insert(id(offset));
+        }
+        set ^= BlazeSet::from_bitmask(vec![0b1000_0001, 0b0000_0010]);
+        assert_eq!(set.len(), bits(&set));
+        assert_eq!(
+            set.iter().map(BlazeMapId::get_offset).collect::<Vec<_>>(),
+            [8, 9, 15, 16, 63]
+        );
+    }
+
+    #[test]
+    fn reserve() {
+        let mut set = BlazeSet::new();
+        set.reserve(100);
+        let capacity = set.bitmask.capacity();
+        assert!(capacity >= 13);
+        for offset in 0..100 {
+            assert!(set.insert(id(offset)));
+        }
+        assert_eq!(set.bitmask.capacity(), capacity);
+
+        for offset in (100..10_000).step_by(7) {
+            assert!(set.insert(id(offset)));
+            assert!(set.contains(id(offset)));
+            assert!(!set.contains(id(offset + 1)));
+        }
+        assert_eq!(set.len(), 100 + (100..10_000).step_by(7).count());
+        assert!((0..100).all(|offset| set.contains(id(offset))));
+    }
+
+    #[test]
+    fn reserve_like() {
+        let mut map = BlazeMap::new();
+        let mut set = BlazeSet::new();
+        set.reserve_like(&map);
+        assert!(set.bitmask.is_empty());
+
+        map.insert(id(20), "20");
+        set.reserve_like(&map);
+        assert_eq!(set.bitmask.len(), 3);
+        assert!(set.is_empty());
+
+        map.insert(id(3), "3");
+        set.reserve_like(&map);
+        assert_eq!(set.bitmask.len(), 3);
+
+        set.insert(id(50));
+        set.reserve_like(&map);
+        assert_eq!(set.bitmask.len(), 7);
+        assert_eq!(
+            set.iter().map(BlazeMapId::get_offset).collect::<Vec<_>>(),
+            [50]
+        );
+    }
+
+    #[test]
+    fn iter_empty() {
+        let mut set = BlazeSet::<Id>::new();
+        assert_eq!(set.iter().next(), None);
+
+        set.insert(id(10));
+        set.remove(id(10));
+        assert!(set.is_empty());
+        assert_eq!(set.iter().len(), 0);
+        assert_eq!(set.iter().next(), None);
+        assert_eq!(set, BlazeSet::new());
+    }
+}
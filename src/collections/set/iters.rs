@@ -0,0 +1,184 @@
+use crate::{
+    collections::set::BlazeSet,
+    prelude::{BlazeMapId, BlazeMapIdStatic},
+    traits::{KeyByOffsetProvider, TypeInfoContainer},
+};
+use core::{
+    borrow::Borrow,
+    fmt::{Debug, Formatter},
+    marker::PhantomData,
+};
+
+/// An iterator over the elements of a [`BlazeSet`].
+///
+/// This `struct` is created by the [`iter`] method on [`BlazeSet`]. See its
+/// documentation for more.
+///
+/// [`iter`]: BlazeSet::iter
+pub struct Iter<'a, K> {
+    pub(in crate::collections::set) bitmask: &'a [u8],
+
+    pub(in crate::collections::set) current_position: usize,
+
+    pub(in crate::collections::set) len: usize,
+
+    pub(in crate::collections::set) phantom: PhantomData<K>,
+}
+
+/// An owning iterator over the elements of a [`BlazeSet`].
+///
+/// This `struct` is created by the [`into_iter`] method on [`BlazeSet`]
+/// (provided by the [`IntoIterator`] trait). See its documentation for more.
+///
+/// [`into_iter`]: IntoIterator::into_iter
+pub struct IntoIter<K> {
+    pub(in crate::collections::set) inner: BlazeSet<K>,
+
+    pub(in crate::collections::set) current_position: usize,
+}
+
+/// Returns the first offset that is not less than `current_position`
+/// and whose bit is set in `bitmask`, and moves `current_position` past it.
+///
+/// # Safety
+/// The `bitmask` must contain at least one set bit at or after
+/// `current_position`.
+#[inline]
+unsafe fn next_set_offset(bitmask: &[u8], current_position: &mut usize) -> usize {
+    loop {
+        let byte = unsafe { *bitmask.get_unchecked(*current_position / 8) };
+        let remaining = byte >> (*current_position % 8);
+        if remaining == 0 {
+            *current_position = (*current_position / 8 + 1) * 8;
+        } else {
+            let offset = *current_position + remaining.trailing_zeros() as usize;
+            *current_position = offset + 1;
+            return offset;
+        }
+    }
+}
+
+impl<K> Iterator for Iter<'_, K>
+where
+    K: BlazeMapId,
+{
+    type Item = K;
+
+    #[inline]
+    fn next(&mut self) -> Option<K> {
+        let Self {
+            bitmask,
+            current_position,
+            len,
+            ..
+        } = self;
+        if *len == 0 {
+            return None;
+        }
+        unsafe {
+            let offset = next_set_offset(bitmask, current_position);
+            *len -= 1;
+            Some(K::from_offset_unchecked(offset))
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<K> ExactSizeIterator for Iter<'_, K>
+where
+    K: BlazeMapId,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<K> Iterator for IntoIter<K>
+where
+    K: BlazeMapId,
+{
+    type Item = K;
+
+    #[inline]
+    fn next(&mut self) -> Option<K> {
+        let Self {
+            inner: BlazeSet { bitmask, len, .. },
+            current_position,
+        } = self;
+        if *len == 0 {
+            return None;
+        }
+        unsafe {
+            let offset = next_set_offset(bitmask, current_position);
+            *bitmask.get_unchecked_mut(offset / 8) &= !(1 << (offset % 8));
+            *len -= 1;
+            Some(K::from_offset_unchecked(offset))
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.inner.len, Some(self.inner.len))
+    }
+}
+
+impl<K> ExactSizeIterator for IntoIter<K>
+where
+    K: BlazeMapId,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.inner.len
+    }
+}
+
+impl<K> Clone for Iter<'_, K> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            bitmask: self.bitmask,
+            current_position: self.current_position,
+            len: self.len,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<K> Debug for Iter<'_, K>
+where
+    K: BlazeMapIdStatic,
+    K::OrigType: Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let key_provider = K::static_container().key_by_offset_provider();
+        let mut debug_list = f.debug_list();
+        for key in self.clone() {
+            let key = unsafe { key_provider.key_by_offset_unchecked(key.get_offset()) };
+            debug_list.entry(key.borrow());
+        }
+        debug_list.finish()
+    }
+}
+
+impl<K> Debug for IntoIter<K>
+where
+    K: BlazeMapIdStatic,
+    K::OrigType: Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let key_provider = K::static_container().key_by_offset_provider();
+        let mut debug_list = f.debug_list();
+        for key in &self.inner {
+            let key = unsafe { key_provider.key_by_offset_unchecked(key.get_offset()) };
+            debug_list.entry(key.borrow());
+        }
+        debug_list.finish()
+    }
+}
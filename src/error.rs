@@ -0,0 +1,20 @@
+use core::fmt::{Display, Formatter};
+
+/// Error returned when registering a new key would exceed
+/// the maximum capacity of a bounded `blazemap` id type defined by
+/// the [`define_key_wrapper_bounded`](crate::define_key_wrapper_bounded)
+/// macro.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CapacityOverflow {
+    /// Maximum capacity of the id type that was exceeded.
+    pub capacity: usize,
+}
+
+impl Display for CapacityOverflow {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "capacity {} overflow", self.capacity)
+    }
+}
+
+impl core::error::Error for CapacityOverflow {}
@@ -3,12 +3,25 @@
 //! and also provides tools
 //! for generating lightweight identifiers that can be type-safely used as keys
 //! for this map.
+//!
+//! Without the default `std` feature, the crate is `no_std` and only
+//! requires `alloc`. In this mode, only the identifiers generated by
+//! [`define_plain_id`] are available.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
 
 /// Collection types.
 pub mod collections;
+/// Error types.
+pub mod error;
 /// Utilities for testing the codebase with [`loom`](crate::external::loom).
 #[cfg(feature = "loom")]
 pub mod loom;
+/// [`proptest`](crate::external::proptest) strategies
+/// for the collection types.
+#[cfg(feature = "proptest")]
+pub mod proptest;
 #[doc(hidden)]
 pub mod sync;
 #[doc(hidden)]
@@ -22,19 +35,33 @@ pub mod utils;
 /// Crate prelude.
 pub mod prelude {
     pub use crate::{
-        collections::map::BlazeMap,
-        traits::{AllInstancesIter, BlazeMapId, BlazeMapIdStatic, BlazeMapIdWrapper},
+        collections::{map::BlazeMap, set::BlazeSet},
+        traits::{
+            AllInstancesIter, BlazeMapId, BlazeMapIdFromOrig, BlazeMapIdStatic, BlazeMapIdWrapper,
+        },
     };
 }
 
 /// Public re-exports of external crates used.
 #[doc(hidden)]
 pub mod external {
+    #[cfg(feature = "proptest")]
+    pub use ::proptest;
+    #[cfg(feature = "arbitrary")]
+    pub use arbitrary;
+    #[cfg(feature = "borsh")]
+    pub use borsh;
+    #[cfg(feature = "rayon")]
+    pub use rayon;
+    #[cfg(feature = "rkyv")]
+    pub use rkyv;
     #[cfg(feature = "serde")]
     pub use serde;
 
     #[cfg(feature = "loom")]
     pub use loom;
+    #[cfg(feature = "std")]
     pub use once_cell;
+    #[cfg(feature = "std")]
     pub use parking_lot;
 }
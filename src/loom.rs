@@ -39,12 +39,9 @@ where
     #[must_use]
     pub fn all_instances_iter(&self) -> AllInstancesIter<I> {
         use crate::traits::CapacityInfoProvider;
-        let num_elems = self
-            .type_info_container
-            .capacity_info_provider()
-            .offset_capacity();
+        let capacity_info = self.type_info_container.capacity_info_provider();
         AllInstancesIter {
-            range: 0..num_elems,
+            range: capacity_info.first_offset()..capacity_info.offset_capacity(),
             phantom: PhantomData,
         }
     }
@@ -0,0 +1,55 @@
+use crate::{
+    collections::map::BlazeMap,
+    prelude::{BlazeMapId, BlazeMapIdStatic, BlazeMapIdWrapper},
+};
+use ::proptest::{
+    collection::{hash_map, SizeRange},
+    strategy::Strategy,
+};
+use core::{fmt::Debug, hash::Hash};
+
+/// Creates a [`Strategy`] generating [`BlazeMap`]s
+/// with the original keys generated by `keys`, the values generated by
+/// `values`, and the number of entries within `size`.
+///
+/// Note that every original key generated gets registered
+/// as an instance of `K`, including the ones generated while shrinking.
+///
+/// # Example
+///
+/// ```rust
+/// use blazemap::{define_key_wrapper, prelude::BlazeMap, proptest::blaze_map_strategy};
+/// use proptest::{prelude::any, proptest};
+///
+/// define_key_wrapper! {
+///     struct Key(u8);
+///     Derive(as for Original Type): {
+///         Debug
+///     }
+/// }
+///
+/// proptest!(|(map in blaze_map_strategy::<Key, _>(any::<u8>(), any::<u32>(), 0..10))| {
+///     assert!(map.len() < 10);
+/// });
+/// ```
+#[inline]
+pub fn blaze_map_strategy<K, V>(
+    keys: impl Strategy<Value = K::OrigType>,
+    values: V,
+    size: impl Into<SizeRange>,
+) -> impl Strategy<Value = BlazeMap<K, V::Value>>
+where
+    K: BlazeMapIdWrapper + BlazeMapIdStatic,
+    <K as BlazeMapId>::OrigType: Debug + Hash + Eq,
+    V: Strategy,
+{
+    hash_map(keys, values, size).prop_map(|entries| {
+        let mut result = BlazeMap::with_current_key_type_capacity();
+        for (key, value) in entries {
+            let key = unsafe { K::new(K::static_container(), key) };
+            result.insert(key, value);
+        }
+        result.shrink_to_fit();
+        result
+    })
+}
@@ -2,7 +2,6 @@
 pub use loom::sync::{atomic::AtomicUsize, atomic::Ordering, RwLock, RwLockReadGuard};
 
 #[cfg(not(feature = "loom"))]
-pub use {
-    parking_lot::RwLock,
-    std::sync::atomic::{AtomicUsize, Ordering},
-};
+pub use core::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(all(feature = "std", not(feature = "loom")))]
+pub use parking_lot::RwLock;
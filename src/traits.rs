@@ -1,4 +1,6 @@
-use std::{
+use crate::error::CapacityOverflow;
+use alloc::vec::Vec;
+use core::{
     borrow::Borrow,
     fmt::{Debug, Formatter},
     hash::{Hash, Hasher},
@@ -33,6 +35,19 @@ pub trait BlazeMapIdWrapper: BlazeMapId {
     /// Creates a new instance of [`Self`] based on the
     /// [`Self::OrigType`](BlazeMapId::OrigType) instance.
     unsafe fn new(type_info_container: &Self::TypeInfoContainer, key: Self::OrigType) -> Self;
+
+    /// Creates a new instance of [`Self`] based on the
+    /// [`Self::OrigType`](BlazeMapId::OrigType) instance.
+    ///
+    /// Returns an error instead of panicking if the registration of a new key
+    /// would exceed the maximum capacity of [`Self`].
+    #[inline]
+    unsafe fn try_new(
+        type_info_container: &Self::TypeInfoContainer,
+        key: Self::OrigType,
+    ) -> Result<Self, CapacityOverflow> {
+        Ok(Self::new(type_info_container, key))
+    }
 }
 
 /// Provides an interface for statically registered `blazemap` id types.
@@ -41,15 +56,106 @@ pub trait BlazeMapIdStatic: BlazeMapId {
     #[inline]
     #[must_use]
     fn all_instances_iter() -> AllInstancesIter<Self> {
-        let num_elems = Self::static_container()
-            .capacity_info_provider()
-            .offset_capacity();
+        let capacity_info = Self::static_container().capacity_info_provider();
         AllInstancesIter {
-            range: 0..num_elems,
+            range: capacity_info.first_offset()..capacity_info.offset_capacity(),
             phantom: PhantomData,
         }
     }
 
+    /// Returns a checkpoint of the registry,
+    /// i.e. the current offset capacity of the type.
+    ///
+    /// Pass it to [`instances_since`](Self::instances_since) later
+    /// to iterate over the identifiers registered after the checkpoint.
+    #[inline]
+    #[must_use]
+    fn checkpoint() -> usize {
+        Self::static_container()
+            .capacity_info_provider()
+            .offset_capacity()
+    }
+
+    /// Creates an iterator over the identifiers registered
+    /// after the [`checkpoint`](Self::checkpoint) specified was taken.
+    #[inline]
+    #[must_use]
+    fn instances_since(checkpoint: usize) -> AllInstancesIter<Self> {
+        let mut result = Self::all_instances_iter();
+        result.range.start = checkpoint.clamp(result.range.start, result.range.end);
+        result
+    }
+
+    /// Creates an iterator over all identifiers registered
+    /// along with their original keys.
+    ///
+    /// The iterator holds a read lock on the registry for its whole lifetime
+    /// (for the types that have one), so registering new identifiers of the
+    /// same type while iterating may deadlock. Identifiers registered after
+    /// the iterator is created aren't visited.
+    #[inline]
+    #[must_use]
+    fn all_keys_iter() -> impl Iterator<Item = (Self, impl Borrow<Self::OrigType>)> {
+        Self::all_with_keys()
+    }
+
+    /// Creates an exact-size iterator over all identifiers registered
+    /// along with their original keys.
+    ///
+    /// Takes a single read guard on the registry up front instead of locking
+    /// it per element, and holds it for the whole lifetime of the iterator
+    /// (for the types that have one), so registering new identifiers of the
+    /// same type while iterating may deadlock. Identifiers registered after
+    /// the iterator is created aren't visited.
+    #[inline]
+    #[must_use]
+    fn all_with_keys() -> impl ExactSizeIterator<Item = (Self, impl Borrow<Self::OrigType>)> {
+        let offsets = Self::all_instances_iter().range;
+        let guard = Self::static_container().key_by_offset_provider();
+        offsets.map(move |offset| unsafe {
+            let key: Self::OrigType = guard.key_by_offset_unchecked(offset).borrow().clone();
+            (Self::from_offset_unchecked(offset), key)
+        })
+    }
+
+    /// Creates an identifier from the offset specified,
+    /// or returns `None` if no identifier with this offset is registered.
+    ///
+    /// A safe alternative to [`BlazeMapId::from_offset_unchecked`]
+    /// suitable for untrusted numeric input.
+    #[inline]
+    #[must_use]
+    fn try_from_offset(offset: usize) -> Option<Self> {
+        let capacity_info = Self::static_container().capacity_info_provider();
+        (capacity_info.first_offset()..capacity_info.offset_capacity())
+            .contains(&offset)
+            .then(|| unsafe { Self::from_offset_unchecked(offset) })
+    }
+
+    /// Returns the current total number of registered unique identifiers.
+    #[inline]
+    #[must_use]
+    fn num_registered() -> usize {
+        let capacity_info = Self::static_container().capacity_info_provider();
+        capacity_info.offset_capacity() - capacity_info.first_offset()
+    }
+
+    /// Forgets all the registered identifiers,
+    /// so that the next registered one starts from the first offset again.
+    ///
+    /// Intended for obtaining a clean slate in isolated tests.
+    ///
+    /// # Safety
+    /// Sound only if there are no outstanding instances of the type,
+    /// i.e. neither identifiers nor collections keyed by them
+    /// are used after the reset, and no other thread is using the type
+    /// concurrently.
+    #[inline]
+    #[cfg(all(feature = "test-reset", not(feature = "loom")))]
+    unsafe fn reset_registry() {
+        Self::static_container().reset();
+    }
+
     /// Returns the static container
     /// that holds all the necessary static information for the [`BlazeMapId`]
     /// type.
@@ -57,12 +163,54 @@ pub trait BlazeMapIdStatic: BlazeMapId {
     fn static_container() -> &'static Self::TypeInfoContainer;
 }
 
+/// Provides a safe way to construct statically registered `blazemap`
+/// key-wrapper id types from their original keys in generic code.
+pub trait BlazeMapIdFromOrig: BlazeMapIdWrapper + BlazeMapIdStatic {
+    /// Creates a new instance of [`Self`] based on the
+    /// [`Self::OrigType`](BlazeMapId::OrigType) instance,
+    /// registering it in the static container if necessary.
+    #[must_use]
+    fn from_orig(key: Self::OrigType) -> Self;
+}
+
+impl<T> BlazeMapIdFromOrig for T
+where
+    T: BlazeMapIdWrapper + BlazeMapIdStatic,
+{
+    #[inline]
+    fn from_orig(key: Self::OrigType) -> Self {
+        unsafe { Self::new(Self::static_container(), key) }
+    }
+}
+
 /// Implements an interface for [`BlazeMapId`] key-wrapper static containers.
 #[doc(hidden)]
 pub trait WrapKey<I: BlazeMapId> {
     /// Creates an instance of [`BlazeMapId`] type that is unique to the given
     /// key.
     fn wrap_key(&self, key: I::OrigType) -> I;
+
+    /// Returns the instance of [`BlazeMapId`] type corresponding to the given
+    /// key if it is already registered. Never registers new keys.
+    fn find_key<Q>(&self, key: &Q) -> Option<I>
+    where
+        I::OrigType: Borrow<Q>,
+        Q: Hash + Eq + ?Sized;
+
+    /// Creates instances of [`BlazeMapId`] type that are unique to the given
+    /// keys, preserving their order.
+    #[inline]
+    fn wrap_keys(&self, keys: impl IntoIterator<Item = I::OrigType>) -> Vec<I> {
+        keys.into_iter().map(|key| self.wrap_key(key)).collect()
+    }
+
+    /// Creates an instance of [`BlazeMapId`] type that is unique to the given
+    /// key. Returns an error if the registration of a new key would exceed
+    /// the maximum capacity of the container.
+    #[inline]
+    fn try_wrap_key(&self, key: I::OrigType) -> Result<I, CapacityOverflow> {
+        Ok(self.wrap_key(key))
+    }
 }
 
 pub trait TypeInfoContainer: 'static {
@@ -81,6 +229,14 @@ pub trait TypeInfoContainer: 'static {
     fn key_by_offset_provider(
         &self,
     ) -> impl Deref<Target = impl KeyByOffsetProvider<Self::OrigType>>;
+
+    /// Forgets all the registered identifiers.
+    ///
+    /// # Safety
+    /// See [`BlazeMapIdStatic::reset_registry`].
+    #[doc(hidden)]
+    #[cfg(all(feature = "test-reset", not(feature = "loom")))]
+    unsafe fn reset(&self);
 }
 
 /// Provides the current total number of registered unique [`BlazeMapId`]
@@ -90,6 +246,13 @@ pub trait CapacityInfoProvider {
     /// Returns the current total number of registered unique [`BlazeMapId`]
     /// identifiers.
     fn offset_capacity(&self) -> usize;
+
+    /// Returns the offset of the first [`BlazeMapId`] identifier.
+    /// Offsets below it are never assigned.
+    #[inline]
+    fn first_offset(&self) -> usize {
+        0
+    }
 }
 
 /// May unsafely return the registered key corresponding to the offset
@@ -118,7 +281,7 @@ impl<T> Clone for AllInstancesIter<T> {
 
 impl<T> Debug for AllInstancesIter<T> {
     #[inline]
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "{:?}", self.range)
     }
 }
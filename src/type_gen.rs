@@ -1,13 +1,17 @@
 #![allow(clippy::module_name_repetitions)]
 
+#[cfg(feature = "std")]
 mod key_wrapper;
+#[cfg(feature = "std")]
 mod key_wrapper_bounded;
 mod plain_id;
 
 #[cfg(all(test, not(feature = "loom")))]
 mod tests {
     use crate::{
-        define_key_wrapper, define_key_wrapper_bounded, define_plain_id, prelude::BlazeMapId,
+        define_key_wrapper, define_key_wrapper_bounded, define_plain_id,
+        error::CapacityOverflow,
+        prelude::{BlazeMap, BlazeMapId, BlazeMapIdFromOrig, BlazeMapIdStatic},
     };
 
     #[cfg(feature = "serde")]
@@ -39,6 +43,7 @@ mod tests {
         }
 
         #[test]
+        #[allow(dead_code)]
         fn plain_id() {
             define_plain_id! {
                 struct BlazeMapIdExample;
@@ -99,6 +104,40 @@ mod tests {
             let _second = BlazeMapKeyExample::new("second".to_string());
             let _third = BlazeMapKeyExample::new("third".to_string());
         }
+
+        #[test]
+        fn key_wrapper_as_offset() {
+            define_key_wrapper! {
+                struct Key(String);
+                Derive(as for Original Type): { Debug };
+                Derive(as for usize): { Serialize, Deserialize }
+            }
+            define_key_wrapper_bounded! {
+                struct BoundedKey(String);
+                MAX_CAP = 2;
+                Derive(as for usize): { Serialize, Deserialize }
+            }
+
+            let _first = Key::new("first".to_string());
+            let second = Key::new("second".to_string());
+            let json = serde_json::to_string(&second).unwrap();
+            assert_eq!(json, "1");
+            assert_eq!(serde_json::from_str::<Key>(&json).unwrap(), second);
+            let err = serde_json::from_str::<Key>("2").unwrap_err();
+            assert!(err.to_string().contains("offset 2 is not registered"));
+            assert!(serde_json::from_str::<Key>("\"second\"").is_err());
+
+            let bounded = BoundedKey::new("first".to_string());
+            let json = serde_json::to_string(&bounded).unwrap();
+            assert_eq!(json, "0");
+            assert_eq!(
+                serde_json::from_str::<BoundedKey>(&json)
+                    .unwrap()
+                    .get_offset(),
+                0
+            );
+            assert!(serde_json::from_str::<BoundedKey>("1").is_err());
+        }
     }
 
     #[test]
@@ -149,6 +188,494 @@ mod tests {
     }
 
     #[test]
+    fn key_wrapper_enum() {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        enum Color {
+            Red,
+            Green,
+            Blue,
+        }
+
+        define_key_wrapper! {
+            struct ColorId(Color);
+            Derive(as for Original Type): {
+                Debug,
+                Ord
+            }
+        }
+
+        let blue = ColorId::new(Color::Blue);
+        let red = ColorId::new(Color::Red);
+        let green = ColorId::new(Color::Green);
+        assert_eq!(blue.get_offset(), 0);
+        assert_eq!(red.get_offset(), 1);
+        assert_eq!(green.get_offset(), 2);
+        assert_eq!(ColorId::new(Color::Blue), blue);
+
+        let mut sorted = [blue, green, red];
+        sorted.sort();
+        assert_eq!(sorted, [red, green, blue]);
+        assert_eq!(
+            format!("{green:?}"),
+            "ColorId { original_key: Green, offset: 2 }"
+        );
+
+        let mut map = BlazeMap::new();
+        map.insert(green, "green");
+        map.insert(blue, "blue");
+        assert_eq!(format!("{map:?}"), r#"{Blue: "blue", Green: "green"}"#);
+    }
+
+    #[test]
+    #[allow(dead_code)]
+    fn from_orig() {
+        fn collect_ids<I: BlazeMapIdFromOrig>(
+            keys: impl IntoIterator<Item = I::OrigType>,
+        ) -> Vec<I> {
+            keys.into_iter().map(I::from_orig).collect()
+        }
+
+        define_key_wrapper! {
+            struct Unbounded(&'static str);
+            Derive(as for Original Type): {
+                Debug
+            }
+        }
+        define_key_wrapper_bounded! {
+            struct Bounded(&'static str);
+            MAX_CAP = 2
+        }
+
+        let ids = collect_ids::<Unbounded>(["first", "second", "first"]);
+        assert_eq!(
+            ids,
+            [
+                Unbounded::new("first"),
+                Unbounded::new("second"),
+                Unbounded::new("first")
+            ]
+        );
+        assert_eq!(
+            ids.iter().map(|id| id.get_offset()).collect::<Vec<_>>(),
+            [0, 1, 0]
+        );
+
+        let ids = collect_ids::<Bounded>(["second", "first", "second"]);
+        assert_eq!(
+            ids.iter().map(|id| id.get_offset()).collect::<Vec<_>>(),
+            [0, 1, 0]
+        );
+        assert_eq!(ids[1].key(), &"first");
+    }
+
+    #[test]
+    fn key_wrapper_byte_slice() {
+        define_key_wrapper! {
+            struct HashId(Box<[u8]>);
+            Derive(as for Original Type): {
+                Debug,
+                Ord
+            }
+        }
+
+        let hashes: [&[u8]; 3] = [&[0xff, 0x01], &[0x00, 0x02, 0x03], &[0x7f]];
+        let ids: Vec<_> = hashes.iter().map(|hash| HashId::new(*hash)).collect();
+        assert_eq!(
+            ids.iter().map(|id| id.get_offset()).collect::<Vec<_>>(),
+            [0, 1, 2]
+        );
+        assert_eq!(HashId::new(&[0x00, 0x02, 0x03][..]), ids[1]);
+
+        let mut sorted = ids.clone();
+        sorted.sort();
+        assert_eq!(sorted, [ids[1], ids[2], ids[0]]);
+
+        let mut map = BlazeMap::new();
+        for (id, hash) in ids.iter().zip(hashes) {
+            map.insert(*id, hash.len());
+        }
+        assert_eq!(map.get(HashId::new(vec![0x7f])), Some(&1));
+        assert_eq!(format!("{map:?}"), "{[255, 1]: 2, [0, 2, 3]: 3, [127]: 1}");
+    }
+
+    #[test]
+    #[allow(dead_code)]
+    fn key_wrapper_bounded_try_new() {
+        define_key_wrapper_bounded! {
+            struct Key(&'static str);
+            MAX_CAP = 2;
+            Derive(as for Original Type): {
+                Debug
+            }
+        }
+
+        let first = Key::try_new("first").unwrap();
+        let second = Key::try_new("second").unwrap();
+        assert_eq!(Key::try_new("first"), Ok(first));
+        assert_eq!(
+            Key::try_new("third").unwrap_err(),
+            CapacityOverflow { capacity: 2 }
+        );
+        assert_eq!(Key::try_new("second"), Ok(second));
+        assert_eq!(Key::all_instances_iter().len(), 2);
+    }
+
+    #[test]
+    fn key_wrapper_hash_as_for_orig() {
+        use std::hash::{BuildHasher, RandomState};
+
+        define_key_wrapper! {
+            struct Unbounded(&'static str);
+            Derive(as for Original Type): {
+                Debug,
+                Hash
+            }
+        }
+        define_key_wrapper_bounded! {
+            struct Bounded(&'static str);
+            MAX_CAP = 4;
+            Derive(as for Original Type): {
+                Hash
+            }
+        }
+        define_key_wrapper! {
+            struct Structural(&'static str)
+        }
+
+        let hasher = RandomState::new();
+        for key in ["first", "second", "third"] {
+            let expected = hasher.hash_one(key);
+            assert_eq!(hasher.hash_one(Unbounded::new(key)), expected);
+            assert_eq!(hasher.hash_one(Bounded::new(key)), expected);
+        }
+        let _ = Structural::new("first");
+        assert_eq!(
+            hasher.hash_one(Structural::new("second")),
+            hasher.hash_one(Structural::new("second"))
+        );
+    }
+
+    #[test]
+    fn key_wrapper_eq_as_for_orig() {
+        define_key_wrapper! {
+            struct Unbounded(&'static str);
+            Derive(as for Original Type): {
+                Debug,
+                PartialEq,
+                Eq,
+                Hash
+            }
+        }
+        define_key_wrapper_bounded! {
+            struct Bounded(&'static str);
+            MAX_CAP = 4;
+            Derive(as for Original Type): {
+                Debug,
+                PartialEq,
+                Eq
+            }
+        }
+
+        assert_eq!(Unbounded::new("first"), Unbounded::new("first"));
+        assert_ne!(Unbounded::new("first"), Unbounded::new("second"));
+        assert_eq!(Bounded::new("first"), Bounded::new("first"));
+        assert_ne!(Bounded::new("first"), Bounded::new("second"));
+
+        let set: std::collections::HashSet<_> = ["first", "second", "first"]
+            .into_iter()
+            .map(Unbounded::new)
+            .collect();
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn key_wrapper_new_into() {
+        define_key_wrapper! {
+            struct Unbounded(String);
+            Derive(as for Original Type): {
+                Debug
+            }
+        }
+        define_key_wrapper_bounded! {
+            struct Bounded(String);
+            MAX_CAP = 2;
+            Derive(as for Original Type): {
+                Debug
+            }
+        }
+
+        let literal = Unbounded::new("first");
+        let runtime = Unbounded::new(format!("fir{}", "st"));
+        assert_eq!(literal, runtime);
+        assert_eq!(runtime.with_key(Clone::clone), "first");
+        assert_ne!(Unbounded::new(String::from("second")), literal);
+
+        let literal = Bounded::new("first");
+        assert_eq!(Bounded::try_new(format!("fir{}", "st")).unwrap(), literal);
+        assert_eq!(literal.key(), "first");
+    }
+
+    #[test]
+    fn key_wrapper_register_all() {
+        define_key_wrapper! {
+            struct Key(&'static str);
+            Derive(as for Original Type): {
+                Debug
+            }
+        }
+
+        let ids = Key::register_all(["zero", "one", "two", "three"]);
+        assert_eq!(
+            ids.iter().map(|id| id.get_offset()).collect::<Vec<_>>(),
+            [0, 1, 2, 3]
+        );
+        assert_eq!(Key::new("two"), ids[2]);
+
+        let ids = Key::register_all(["four", "one", "five", "four"]);
+        assert_eq!(
+            ids.iter().map(|id| id.get_offset()).collect::<Vec<_>>(),
+            [4, 1, 5, 4]
+        );
+        assert!(Key::register_all([]).is_empty());
+    }
+
+    #[test]
+    #[allow(dead_code)]
+    fn num_registered() {
+        define_key_wrapper! {
+            struct Key(&'static str)
+        }
+        define_plain_id! {
+            struct Id
+        }
+
+        assert_eq!(Key::num_registered(), 0);
+        for key in ["first", "second", "third", "second"] {
+            let _ = Key::new(key);
+        }
+        assert_eq!(Key::num_registered(), 3);
+
+        for _ in 0..3 {
+            let _ = Id::new();
+        }
+        assert_eq!(Id::num_registered(), 3);
+    }
+
+    #[test]
+    #[allow(dead_code)]
+    fn niche_optimized() {
+        use std::mem::size_of;
+
+        define_plain_id! {
+            struct PlainId;
+            NicheOptimized;
+            Derive: {
+                Ord
+            }
+        }
+        define_key_wrapper! {
+            struct Unbounded(String);
+            NicheOptimized;
+            Derive(as for Original Type): {
+                Debug
+            }
+        }
+        define_key_wrapper_bounded! {
+            struct Bounded(&'static str);
+            MAX_CAP = 4;
+            NicheOptimized
+        }
+        define_plain_id! {
+            struct Regular
+        }
+
+        assert_eq!(size_of::<Option<PlainId>>(), size_of::<PlainId>());
+        assert_eq!(size_of::<Option<Unbounded>>(), size_of::<Unbounded>());
+        assert_eq!(size_of::<Option<Bounded>>(), size_of::<Bounded>());
+        assert_eq!(size_of::<PlainId>(), size_of::<Regular>());
+        assert_ne!(size_of::<Option<Regular>>(), size_of::<Regular>());
+
+        let first = PlainId::new();
+        let second = PlainId::new();
+        assert_eq!((first.get_offset(), second.get_offset()), (0, 1));
+        assert!(first < second);
+        assert_eq!(unsafe { PlainId::from_offset_unchecked(1) }, second);
+
+        let first = Unbounded::new("first");
+        let second = Unbounded::new("second");
+        assert_eq!((first.get_offset(), second.get_offset()), (0, 1));
+        assert_eq!(
+            format!("{second:?}"),
+            r#"Unbounded { original_key: "second", offset: 1 }"#
+        );
+
+        let mut map = BlazeMap::new();
+        map.insert(Bounded::new("first"), 1);
+        map.insert(Bounded::new("second"), 2);
+        assert_eq!(map.get(Bounded::new("second")), Some(&2));
+        assert_eq!(Bounded::new("second").get_offset(), 1);
+    }
+
+    #[test]
+    fn bounded_container_capacity() {
+        define_key_wrapper_bounded! {
+            struct Key(&'static str);
+            MAX_CAP = 1_000
+        }
+
+        assert_eq!(Key::used_capacity(), 0);
+        assert_eq!(Key::max_capacity(), 1_000);
+        let _ = Key::new("first");
+        let _ = Key::new("second");
+        let _ = Key::new("first");
+        assert_eq!(Key::used_capacity(), 2);
+        assert_eq!(Key::max_capacity(), 1_000);
+        assert_eq!(Key::static_container().used_capacity(), 2);
+    }
+
+    #[test]
+    fn bounded_container_registered_keys() {
+        define_key_wrapper_bounded! {
+            struct Key(String);
+            MAX_CAP = 8
+        }
+
+        assert_eq!(Key::registered_keys().len(), 0);
+        let _ = Key::new("first");
+        let _ = Key::new("second");
+        let _ = Key::new("first");
+        let _ = Key::new("third");
+
+        let keys: Vec<(usize, &String)> = Key::registered_keys().collect();
+        assert_eq!(
+            keys,
+            [
+                (0, &"first".to_string()),
+                (1, &"second".to_string()),
+                (2, &"third".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn all_keys_iter() {
+        use std::borrow::Borrow;
+
+        define_key_wrapper! {
+            struct Unbounded(String)
+        }
+        define_key_wrapper_bounded! {
+            struct Bounded(&'static str);
+            MAX_CAP = 4
+        }
+
+        let names = ["first", "second", "third"];
+        for name in names {
+            let _ = Unbounded::new(name);
+            let _ = Bounded::new(name);
+        }
+        let unbounded: Vec<_> = Unbounded::all_keys_iter()
+            .map(|(id, key)| (id.get_offset(), key.borrow().clone()))
+            .collect();
+        assert_eq!(
+            unbounded,
+            names
+                .iter()
+                .enumerate()
+                .map(|(offset, name)| (offset, name.to_string()))
+                .collect::<Vec<_>>()
+        );
+        let bounded: Vec<_> = Bounded::all_keys_iter()
+            .map(|(id, key)| (id.get_offset(), *key.borrow()))
+            .collect();
+        assert_eq!(bounded, [(0, "first"), (1, "second"), (2, "third")]);
+    }
+
+    #[test]
+    fn all_with_keys() {
+        use std::borrow::Borrow;
+
+        define_key_wrapper! {
+            struct Key(String);
+            Derive(as for Original Type): {
+                Debug
+            }
+        }
+
+        let keys: Vec<_> = ["first", "second", "third"]
+            .into_iter()
+            .map(|name| Key::new(name.to_string()))
+            .collect();
+        let iter = Key::all_with_keys();
+        assert_eq!(iter.len(), 3);
+        let entries: Vec<_> = iter
+            .map(|(key, orig)| (key, orig.borrow().clone()))
+            .collect();
+        assert_eq!(
+            entries,
+            [
+                (keys[0], "first".to_string()),
+                (keys[1], "second".to_string()),
+                (keys[2], "third".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn key_wrapper_with_key() {
+        define_key_wrapper! {
+            struct Key(String)
+        }
+
+        let first = Key::new("first".to_string());
+        let second = Key::new("second".to_string());
+        assert_eq!(first.with_key(Clone::clone), "first");
+        assert_eq!(second.with_key(String::len), 6);
+        assert!(Key::new("first".to_string()).with_key(|key| key == "first"));
+    }
+
+    #[test]
+    fn key_wrapper_debug_unregistered() {
+        define_key_wrapper! {
+            struct Key(String);
+            Derive(as for Original Type): {
+                Debug
+            }
+        }
+
+        define_key_wrapper_bounded! {
+            struct KeyBounded(String);
+            MAX_CAP = 2;
+            Derive(as for Original Type): {
+                Debug
+            }
+        }
+
+        let key = Key::new("first".to_string());
+        assert_eq!(
+            format!("{key:?}"),
+            r#"Key { original_key: "first", offset: 0 }"#
+        );
+        let key = unsafe { Key::from_offset_unchecked(100) };
+        assert_eq!(
+            format!("{key:?}"),
+            "Key { original_key: <unregistered offset 100>, offset: 100 }"
+        );
+
+        let key = KeyBounded::new("first".to_string());
+        assert_eq!(
+            format!("{key:?}"),
+            r#"KeyBounded { original_key: "first", offset: 0 }"#
+        );
+        let key = unsafe { KeyBounded::from_offset_unchecked(1) };
+        assert_eq!(
+            format!("{key:?}"),
+            "KeyBounded { original_key: <unregistered offset 1>, offset: 1 }"
+        );
+    }
+
+    #[test]
+    #[allow(dead_code)]
     fn plain_id() {
         define_plain_id! {
             struct BlazeMapIdExample1;
@@ -175,6 +702,464 @@ mod tests {
         assert_eq!(second.get_offset(), 1);
     }
 
+    #[test]
+    #[cfg(feature = "test-reset")]
+    #[allow(dead_code)]
+    fn reset_registry() {
+        define_key_wrapper! {
+            struct Key(String)
+        }
+        define_key_wrapper_bounded! {
+            struct KeyBounded(String);
+            MAX_CAP = 2
+        }
+        define_plain_id! {
+            struct Id;
+            StartFrom: 1
+        }
+
+        for key in ["first", "second"] {
+            let _ = Key::new(key.to_string());
+            let _ = KeyBounded::new(key.to_string());
+            let _ = Id::new();
+        }
+        assert_eq!(Key::num_registered(), 2);
+        assert_eq!(KeyBounded::num_registered(), 2);
+        assert_eq!(Id::num_registered(), 2);
+
+        unsafe {
+            Key::reset_registry();
+            KeyBounded::reset_registry();
+            Id::reset_registry();
+        }
+        assert_eq!(Key::num_registered(), 0);
+        assert_eq!(KeyBounded::num_registered(), 0);
+        assert_eq!(Id::num_registered(), 0);
+
+        assert_eq!(Key::new("second".to_string()).get_offset(), 0);
+        assert_eq!(KeyBounded::new("second".to_string()).get_offset(), 0);
+        assert_eq!(KeyBounded::new("third".to_string()).get_offset(), 1);
+        assert_eq!(Id::new().get_offset(), 1);
+    }
+
+    #[test]
+    #[allow(dead_code)]
+    fn try_from_offset() {
+        define_key_wrapper! {
+            struct Key(&'static str);
+            Derive(as for Original Type): {
+                Debug
+            }
+        }
+        define_plain_id! {
+            struct Id;
+            StartFrom: 2
+        }
+
+        assert_eq!(Key::try_from_offset(0), None);
+        let first = Key::new("first");
+        let second = Key::new("second");
+        assert_eq!(Key::try_from_offset(0), Some(first));
+        assert_eq!(Key::try_from_offset(1), Some(second));
+        assert_eq!(Key::try_from_offset(2), None);
+
+        let id = Id::new();
+        assert_eq!(Id::try_from_offset(2), Some(id));
+        assert_eq!(Id::try_from_offset(1), None);
+        assert_eq!(Id::try_from_offset(3), None);
+        assert_eq!(Id::try_from_offset(usize::MAX), None);
+    }
+
+    #[test]
+    fn instances_since() {
+        define_key_wrapper! {
+            struct Key(&'static str);
+            Derive(as for Original Type): {
+                Debug
+            }
+        }
+
+        let start = Key::checkpoint();
+        assert_eq!(start, 0);
+        let first_phase: Vec<_> = ["a", "b", "c"].into_iter().map(Key::new).collect();
+        let checkpoint = Key::checkpoint();
+        assert_eq!(checkpoint, 3);
+        assert_eq!(Key::instances_since(checkpoint).len(), 0);
+        let second_phase: Vec<_> = ["b", "d", "e"].into_iter().map(Key::new).collect();
+
+        assert_eq!(
+            Key::instances_since(checkpoint).collect::<Vec<_>>(),
+            second_phase[1..]
+        );
+        assert_eq!(
+            Key::instances_since(start).take(3).collect::<Vec<_>>(),
+            first_phase
+        );
+        assert_eq!(Key::instances_since(100).len(), 0);
+    }
+
+    #[test]
+    #[allow(dead_code)]
+    fn attributes_passthrough() {
+        #[cfg(test)]
+        define_plain_id! {
+            /// Documented plain identifier.
+            #[must_use]
+            #[allow(clippy::upper_case_acronyms)]
+            struct PID;
+            Derive: {
+                Ord
+            }
+        }
+
+        #[cfg(not(test))]
+        define_plain_id! {
+            struct PID
+        }
+
+        define_key_wrapper! {
+            /// Documented key wrapper.
+            #[allow(clippy::upper_case_acronyms)]
+            struct KW(&'static str);
+            Derive(as for Original Type): {
+                Debug
+            }
+        }
+
+        define_key_wrapper_bounded! {
+            /// Documented bounded key wrapper.
+            #[allow(clippy::upper_case_acronyms)]
+            struct KWB(&'static str);
+            MAX_CAP = 1
+        }
+
+        let first = PID::new();
+        assert!(first < PID::new());
+        assert_eq!(KW::new("first"), KW::new("first"));
+        assert_eq!(KWB::new("first").get_offset(), 0);
+    }
+
+    #[test]
+    fn concurrent_registration_order() {
+        use std::collections::HashMap;
+
+        define_key_wrapper! {
+            struct Key(usize)
+        }
+        define_key_wrapper_bounded! {
+            struct KeyBounded(usize);
+            MAX_CAP = 256
+        }
+
+        const NUM_THREADS: usize = 8;
+        const NUM_KEYS: usize = 256;
+
+        let registered: Vec<Vec<(usize, usize, usize)>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..NUM_THREADS)
+                .map(|thread| {
+                    scope.spawn(move || {
+                        (0..NUM_KEYS)
+                            .map(|i| (i * (2 * thread + 1) + thread * 37) % NUM_KEYS)
+                            .map(|key| {
+                                let offset = Key::new(key).get_offset();
+                                let bounded_offset = KeyBounded::new(key).get_offset();
+                                (key, offset, bounded_offset)
+                            })
+                            .collect()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect()
+        });
+
+        let mut offsets = HashMap::new();
+        let mut bounded_offsets = HashMap::new();
+        for (key, offset, bounded_offset) in registered.into_iter().flatten() {
+            assert_eq!(*offsets.entry(key).or_insert(offset), offset);
+            assert_eq!(
+                *bounded_offsets.entry(key).or_insert(bounded_offset),
+                bounded_offset
+            );
+        }
+        for offsets in [offsets, bounded_offsets] {
+            assert_eq!(offsets.len(), NUM_KEYS);
+            let mut sorted: Vec<_> = offsets.into_values().collect();
+            sorted.sort_unstable();
+            assert_eq!(sorted, (0..NUM_KEYS).collect::<Vec<_>>());
+        }
+        assert_eq!(Key::num_registered(), NUM_KEYS);
+        assert_eq!(KeyBounded::num_registered(), NUM_KEYS);
+    }
+
+    #[test]
+    fn get_existing() {
+        define_key_wrapper! {
+            struct Bytes(Vec<u8>);
+            Derive(as for Original Type): { Debug }
+        }
+        define_key_wrapper_bounded! {
+            struct BoundedName(String);
+            MAX_CAP = 4;
+            Derive(as for Original Type): { Debug }
+        }
+
+        assert!(Bytes::get_existing(b"first".as_slice()).is_none());
+        let first = Bytes::new(b"first".to_vec());
+        assert_eq!(Bytes::get_existing(b"first".as_slice()), Some(first));
+        assert!(Bytes::get_existing(b"second".as_slice()).is_none());
+        assert_eq!(Bytes::num_registered(), 1);
+
+        assert!(BoundedName::get_existing("first").is_none());
+        let first = BoundedName::new("first");
+        assert_eq!(BoundedName::get_existing("first"), Some(first));
+        assert_eq!(BoundedName::num_registered(), 1);
+    }
+
+    #[test]
+    #[allow(dead_code)]
+    fn repr_u32() {
+        use core::mem::size_of;
+
+        define_plain_id! {
+            struct Id;
+            Repr: u32
+        }
+        define_plain_id! {
+            struct NicheId;
+            NicheOptimized;
+            Repr: u32;
+            StartFrom: 3;
+            Derive: { Ord }
+        }
+        define_key_wrapper! {
+            struct Key(String);
+            Repr: u32;
+            Derive(as for Original Type): { Debug }
+        }
+        define_key_wrapper_bounded! {
+            struct BoundedKey(String);
+            MAX_CAP = 4;
+            NicheOptimized;
+            Repr: u32
+        }
+
+        assert_eq!(size_of::<Id>(), 4);
+        assert_eq!(size_of::<NicheId>(), 4);
+        assert_eq!(size_of::<Option<NicheId>>(), 4);
+        assert_eq!(size_of::<Key>(), 4);
+        assert_eq!(size_of::<Option<BoundedKey>>(), 4);
+
+        let ids: Vec<_> = (0..3).map(|_| Id::new()).collect();
+        assert_eq!(
+            ids.iter().map(|id| id.get_offset()).collect::<Vec<_>>(),
+            [0, 1, 2]
+        );
+        let niche_id = NicheId::new();
+        assert_eq!(niche_id.get_offset(), 3);
+        assert_eq!(unsafe { NicheId::from_offset_unchecked(3) }.get_offset(), 3);
+        let first = Key::new("first");
+        let second = Key::new("second");
+        assert_eq!((first.get_offset(), second.get_offset()), (0, 1));
+        assert_eq!(Key::new("second"), second);
+        assert_eq!(BoundedKey::new("first").get_offset(), 0);
+
+        let mut map = BlazeMap::new();
+        map.insert(second, 2);
+        map.insert(first, 1);
+        assert_eq!(format!("{map:?}"), r#"{"first": 1, "second": 2}"#);
+    }
+
+    #[test]
+    #[allow(dead_code)]
+    fn key_wrapper_offset_limit() {
+        use crate::{
+            sync::RwLock, traits::WrapKey, type_info_containers::key_wrapper::StaticContainer,
+        };
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        define_key_wrapper! {
+            struct Key(String);
+            Repr: u32
+        }
+
+        let container = RwLock::new(StaticContainer::<String>::with_max_offset(1));
+        let first: Key = container.wrap_key("first".into());
+        let second: Key = container.wrap_key("second".into());
+        assert_eq!((first.get_offset(), second.get_offset()), (0, 1));
+
+        let overflow = catch_unwind(AssertUnwindSafe(|| {
+            let _: Key = container.wrap_key("third".into());
+        }));
+        assert!(overflow.is_err());
+        // The rejected key isn't registered.
+        let capacity = {
+            use crate::traits::{CapacityInfoProvider, TypeInfoContainer};
+            container.capacity_info_provider().offset_capacity()
+        };
+        assert_eq!(capacity, 2);
+        let _: Key = container.wrap_key("first".into());
+    }
+
+    #[test]
+    #[should_panic(expected = "u32 overflow")]
+    #[allow(dead_code)]
+    fn repr_u32_overflow() {
+        define_plain_id! {
+            struct Id;
+            Repr: u32
+        }
+
+        let _ = unsafe { Id::from_offset_unchecked(u32::MAX as usize + 1) };
+    }
+
+    #[test]
+    fn with_keys_cached() {
+        define_key_wrapper! {
+            struct Key(String);
+            Derive(as for Original Type): { Debug }
+        }
+
+        let keys: Vec<_> = (0..100).map(|idx| Key::new(idx.to_string())).collect();
+        let resolved = Key::with_keys_cached(|cache| {
+            // The whole closure runs under a single read lock.
+            assert!(Key::static_container().try_write().is_none());
+            assert_eq!(cache.len(), 100);
+            assert!(!cache.is_empty());
+            assert_eq!(cache.get("42"), Some(keys[42]));
+            assert_eq!(cache.get("100"), None);
+            keys.iter()
+                .map(|key| cache.key(*key).clone())
+                .collect::<Vec<_>>()
+        });
+        assert!(Key::static_container().try_write().is_some());
+        assert_eq!(
+            resolved,
+            (0..100).map(|idx| idx.to_string()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    #[allow(dead_code)]
+    fn plain_id_try_new() {
+        define_plain_id! {
+            struct Id;
+            StartFrom: usize::MAX - 1
+        }
+        define_plain_id! {
+            struct NicheId;
+            NicheOptimized;
+            StartFrom: usize::MAX - 1
+        }
+        define_plain_id! {
+            struct SmallId;
+            Repr: u32;
+            StartFrom: u32::MAX as usize
+        }
+
+        assert_eq!(Id::try_new().unwrap().get_offset(), usize::MAX - 1);
+        assert!(Id::try_new().is_none());
+        assert!(Id::try_new().is_none());
+        assert_eq!(NicheId::try_new().unwrap().get_offset(), usize::MAX - 1);
+        assert!(NicheId::try_new().is_none());
+        assert_eq!(SmallId::try_new().unwrap().get_offset(), u32::MAX as usize);
+        assert!(SmallId::try_new().is_none());
+        assert!(SmallId::try_new().is_none());
+
+        // Failed attempts don't reserve offsets.
+        assert_eq!(Id::num_registered(), 1);
+        assert_eq!(SmallId::num_registered(), 1);
+        assert_eq!(SmallId::all_instances_iter().count(), 1);
+        assert_eq!(
+            SmallId::all_instances_iter()
+                .map(BlazeMapId::get_offset)
+                .collect::<Vec<_>>(),
+            [u32::MAX as usize]
+        );
+        assert!(SmallId::try_from_offset(u32::MAX as usize).is_some());
+        assert!(SmallId::try_from_offset(u32::MAX as usize + 1).is_none());
+    }
+
+    #[test]
+    #[allow(dead_code)]
+    fn key_wrapper_convert_from() {
+        define_key_wrapper! {
+            struct Key(String);
+            Derive(as for Original Type): { Debug };
+            Convert: { From }
+        }
+        define_key_wrapper! {
+            struct NicheKey(String);
+            NicheOptimized;
+            Derive(as for usize): { Ord };
+            Derive(as for Original Type): { Debug };
+            Convert: { From, }
+        }
+
+        let key: Key = "first".to_string().into();
+        assert_eq!(key, Key::new("first"));
+        assert_eq!(String::from(key), "first");
+        let second = Key::from("second".to_string());
+        assert_eq!(second.get_offset(), 1);
+        let orig: String = second.into();
+        assert_eq!(orig, "second");
+
+        let niche_key = NicheKey::from("niche".to_string());
+        assert_eq!(String::from(niche_key), "niche");
+    }
+
+    #[test]
+    fn plain_id_new_n() {
+        define_plain_id! {
+            struct Id
+        }
+
+        let first = Id::new();
+        let block = Id::new_n(4);
+        assert_eq!(block.len(), 4);
+        assert_eq!(block.map(Id::get_offset).collect::<Vec<_>>(), [1, 2, 3, 4]);
+        assert_eq!(Id::new_n(0).len(), 0);
+        assert_eq!(first.get_offset(), 0);
+        assert_eq!(Id::new().get_offset(), 5);
+        assert_eq!(Id::num_registered(), 6);
+    }
+
+    #[test]
+    #[allow(dead_code)]
+    fn plain_id_start_from() {
+        define_plain_id! {
+            struct Id;
+            StartFrom: 3;
+            Derive: {
+                Ord
+            }
+        }
+
+        define_plain_id! {
+            struct IdNiche;
+            NicheOptimized;
+            StartFrom: 1
+        }
+
+        assert_eq!(Id::num_registered(), 0);
+        let first = Id::new();
+        let second = Id::new();
+        assert_eq!(first.get_offset(), 3);
+        assert_eq!(second.get_offset(), 4);
+        assert_eq!(Id::num_registered(), 2);
+        assert_eq!(
+            Id::all_instances_iter().collect::<Vec<_>>(),
+            [first, second]
+        );
+        let map = BlazeMap::from_keys_with(|key: Id| key.get_offset());
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.iter().collect::<Vec<_>>(), [(first, &3), (second, &4)]);
+
+        assert_eq!(IdNiche::new().get_offset(), 1);
+    }
+
     #[test]
     fn key_wrapper_bounded() {
         define_key_wrapper_bounded! {
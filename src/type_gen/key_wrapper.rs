@@ -1,6 +1,15 @@
 /// Creates a new type that acts as an `usize`-based replacement for the old
 /// type that can be used as a key for `blazemap` collections.
 ///
+/// The old type can be any `'static + Clone + Eq + Hash` type,
+/// e.g. a string, a byte slice such as `Box<[u8]>` or a field-less `enum`.
+///
+/// Offsets are assigned contiguously starting from `0`, strictly in the order
+/// in which distinct original keys get registered for the first time.
+/// Concurrent registrations of the same key from different threads
+/// always resolve to a single offset, since the registry re-checks the key
+/// under its write lock before assigning a new offset.
+///
 /// This macro supports optional inference of standard traits using the
 /// following syntax:
 ///
@@ -17,8 +26,19 @@
 ///     `PartialOrd`)
 ///   * `Debug`
 ///   * `Display`
+///   * `Hash` (hashes the original key instead of the offset, so that the hash
+///     matches the one of the original key)
+///   * `PartialEq` (compares the original keys instead of the offsets; since
+///     the static registry assigns exactly one offset to each original key,
+///     the result is always the same as that of the default comparison of the
+///     offsets, so this only adds the overhead of the `.read` call)
+///   * `Eq`
 ///   * `Serialize` (with `serde` feature only)
 ///   * `Deserialize` (with `serde` feature only)
+///   * `BorshSerialize` (with `borsh` feature only)
+///   * `BorshDeserialize` (with `borsh` feature only)
+///   * `Arbitrary` (with `arbitrary` feature only; registers the generated
+///     original key)
 /// * `Derive(as for usize)` — derives traits in the same way as for the serial
 ///   number assigned when registering an instance of the original type the
 ///   first time
@@ -30,6 +50,46 @@
 ///   * `PartialOrd` (mutually exclusive with `Ord`)
 ///   * `Ord` (also derives `PartialOrd`, so mutually exclusive with
 ///     `PartialOrd`)
+///   * `Serialize` (with `serde` feature only; emits the bare offset, so
+///     mutually exclusive with `Serialize` derived as for the original type)
+///   * `Deserialize` (with `serde` feature only; accepts the bare offset of an
+///     already registered instance, so mutually exclusive with `Deserialize`
+///     derived as for the original type)
+///
+///   The offsets are only meaningful within a single process, so the
+///   serialized identifiers mustn't cross the boundary of the registry
+///   that assigned them.
+///
+/// The optional `NicheOptimized` marker, placed right after the type
+/// declaration, makes the type store its offset as a [`NonZeroUsize`]
+/// (offset + 1), so that `Option` of the type takes no extra space.
+///
+/// The optional `Repr: u32` section, placed after the `NicheOptimized`
+/// marker (if any), makes the type store its offset as a `u32`
+/// (or a [`NonZeroU32`] together with `NicheOptimized`), halving its size
+/// on 64-bit targets. Creating an instance with an offset that doesn't fit
+/// into `u32` panics.
+///
+/// The optional `Convert` section, placed last, generates conversions
+/// between the type and its original type. It supports:
+/// * `From` — implements `From<OrigType>` for the type, registering the key
+///   as [`new`](crate::prelude::BlazeMapIdWrapper::new) does, and
+///   `From<Type>` for the original type, cloning the registered key out
+///   under a read lock on the registry.
+///
+/// [`NonZeroUsize`]: core::num::NonZeroUsize
+/// [`NonZeroU32`]: core::num::NonZeroU32
+///
+/// # Attributes
+///
+/// Outer attributes placed before the type declaration, including doc
+/// comments, are applied to the generated struct ahead of its built-in
+/// `#[derive(Clone, Copy)]`. Besides `Clone` and `Copy`, the macro always implements
+/// `PartialEq`, `Eq` and `Hash` (by offset, unless derived as for the original
+/// type), so these names are reserved along with the ones supported by the
+/// `Derive` sections, which should be used instead.
+/// As with [`define_plain_id`](crate::define_plain_id), `#[cfg(...)]` belongs
+/// on the macro invocation itself.
 ///
 /// # Example
 ///
@@ -60,37 +120,91 @@
 /// ```
 #[macro_export]
 macro_rules! define_key_wrapper {
+    (
+        $(#[$attrs:meta])*
+        $vis:vis
+        struct $new_type:ident($orig_type:ty);
+        NicheOptimized
+        $(; Repr: $repr:ident)?
+        $(; Derive(as for Original Type): {$($to_derive_orig:ident),+ $(,)?} )?
+        $(; Derive(as for usize):         {$(  $to_derive_sn:ident),+ $(,)?} )?
+        $(; Convert: {$($to_convert:ident),+ $(,)?} )?
+        $(;)?
+    ) => {
+        $crate::key_wrapper_inner! {
+            $(#[$attrs])*
+            $vis
+            struct $new_type($orig_type);
+            OFFSET = $crate::offset_repr!(NicheOptimized $($repr)?)
+        }
+        $crate::key_wrapper_structural_derive! {$new_type [$($($to_derive_orig)*)?]}
+        $($($crate::key_wrapper_derive!     {@DERIVE $to_derive_orig $new_type})*)?
+        $($($crate::assigned_offset_derive! {@DERIVE   $to_derive_sn $new_type})*)?
+        $($($crate::key_wrapper_convert!    {@CONVERT  $to_convert $new_type($orig_type)})*)?
+    };
+    (
+        $(#[$attrs:meta])*
+        $vis:vis
+        struct $new_type:ident($orig_type:ty);
+        NicheOptimized
+        $(; Repr: $repr:ident)?
+        $(; Derive(as for usize):         {$(  $to_derive_sn:ident),+ $(,)?} )?
+        $(; Derive(as for Original Type): {$($to_derive_orig:ident),+ $(,)?} )?
+        $(; Convert: {$($to_convert:ident),+ $(,)?} )?
+        $(;)?
+    ) => {
+        $crate::key_wrapper_inner! {
+            $(#[$attrs])*
+            $vis
+            struct $new_type($orig_type);
+            OFFSET = $crate::offset_repr!(NicheOptimized $($repr)?)
+        }
+        $crate::key_wrapper_structural_derive! {$new_type [$($($to_derive_orig)*)?]}
+        $($($crate::key_wrapper_derive!     {@DERIVE $to_derive_orig $new_type})*)?
+        $($($crate::assigned_offset_derive! {@DERIVE   $to_derive_sn $new_type})*)?
+        $($($crate::key_wrapper_convert!    {@CONVERT  $to_convert $new_type($orig_type)})*)?
+    };
     (
         $(#[$attrs:meta])*
         $vis:vis
         struct $new_type:ident($orig_type:ty)
+        $(; Repr: $repr:ident)?
         $(; Derive(as for Original Type): {$($to_derive_orig:ident),+ $(,)?} )?
         $(; Derive(as for usize):         {$(  $to_derive_sn:ident),+ $(,)?} )?
+        $(; Convert: {$($to_convert:ident),+ $(,)?} )?
         $(;)?
     ) => {
         $crate::key_wrapper_inner! {
             $(#[$attrs])*
             $vis
-            struct $new_type($orig_type)
+            struct $new_type($orig_type);
+            OFFSET = $crate::offset_repr!($($repr)?)
         }
+        $crate::key_wrapper_structural_derive! {$new_type [$($($to_derive_orig)*)?]}
         $($($crate::key_wrapper_derive!     {@DERIVE $to_derive_orig $new_type})*)?
         $($($crate::assigned_offset_derive! {@DERIVE   $to_derive_sn $new_type})*)?
+        $($($crate::key_wrapper_convert!    {@CONVERT  $to_convert $new_type($orig_type)})*)?
     };
     (
         $(#[$attrs:meta])*
         $vis:vis
         struct $new_type:ident($orig_type:ty)
+        $(; Repr: $repr:ident)?
         $(; Derive(as for usize):         {$(  $to_derive_sn:ident),+ $(,)?} )?
         $(; Derive(as for Original Type): {$($to_derive_orig:ident),+ $(,)?} )?
+        $(; Convert: {$($to_convert:ident),+ $(,)?} )?
         $(;)?
     ) => {
         $crate::key_wrapper_inner! {
             $(#[$attrs])*
             $vis
-            struct $new_type($orig_type)
+            struct $new_type($orig_type);
+            OFFSET = $crate::offset_repr!($($repr)?)
         }
+        $crate::key_wrapper_structural_derive! {$new_type [$($($to_derive_orig)*)?]}
         $($($crate::key_wrapper_derive!     {@DERIVE $to_derive_orig $new_type})*)?
         $($($crate::assigned_offset_derive! {@DERIVE   $to_derive_sn $new_type})*)?
+        $($($crate::key_wrapper_convert!    {@CONVERT  $to_convert $new_type($orig_type)})*)?
     }
 }
 
@@ -100,21 +214,98 @@ macro_rules! key_wrapper_inner {
     (
         $(#[$attrs:meta])*
         $vis:vis
-        struct $new_type:ident($orig_type:ty)
+        struct $new_type:ident($orig_type:ty);
+        OFFSET = $offset_type:ty
     ) => {
         $(#[$attrs])*
-        #[derive(Clone, Copy, Eq, PartialEq, Hash)]
+        #[derive(Clone, Copy)]
         #[repr(transparent)]
-        $vis struct $new_type($crate::utils::OffsetProvider<usize>);
+        $vis struct $new_type($crate::utils::OffsetProvider<$offset_type>);
 
         #[cfg(not(feature = "loom"))]
         impl $new_type
         {
             #[doc = ::std::concat!("Creates a new instance of [`", ::std::stringify!($new_type), "`].")]
             #[inline]
-            $vis fn new(value: $orig_type) -> Self {
+            $vis fn new(value: impl ::std::convert::Into<$orig_type>) -> Self {
                 use $crate::traits::BlazeMapIdStatic;
-                unsafe { <Self as $crate::prelude::BlazeMapIdWrapper>::new(Self::static_container(), value) }
+                unsafe { <Self as $crate::prelude::BlazeMapIdWrapper>::new(Self::static_container(), value.into()) }
+            }
+
+            #[doc = ::std::concat!(
+                "Returns the [`",
+                ::std::stringify!($new_type),
+                "`] instance corresponding to the given key if it is already registered, ",
+                "and `None` otherwise.\n\n",
+                "Unlike [`",
+                ::std::stringify!($new_type),
+                "::new`], never registers the key, and accepts any borrowed form of the original type, ",
+                "so looking up an interned `Vec<u8>` by a `&[u8]` doesn't allocate."
+            )]
+            #[inline]
+            #[allow(dead_code)]
+            $vis fn get_existing<Q>(key: &Q) -> ::std::option::Option<Self>
+            where
+                $orig_type: ::std::borrow::Borrow<Q>,
+                Q: ::std::hash::Hash + ::std::cmp::Eq + ?::std::marker::Sized,
+            {
+                use $crate::traits::{BlazeMapIdStatic, WrapKey};
+                Self::static_container().find_key(key)
+            }
+
+            #[doc = ::std::concat!(
+                "Registers all the given keys at once, taking the [`",
+                ::std::stringify!($new_type),
+                "`] registry write lock only once, ",
+                "and returns their identifiers in the input order.\n\n",
+                "New keys get sequential offsets in the input order, ",
+                "so registering a vocabulary in a fresh registry yields the offsets `0..n`. ",
+                "Already registered keys keep their offsets."
+            )]
+            #[inline]
+            #[allow(dead_code)]
+            $vis fn register_all(keys: impl ::std::iter::IntoIterator<Item = $orig_type>) -> ::std::vec::Vec<Self> {
+                use $crate::traits::{BlazeMapIdStatic, WrapKey};
+                Self::static_container().wrap_keys(keys)
+            }
+
+            #[doc = ::std::concat!(
+                "Calls `f` with a cache resolving [`",
+                ::std::stringify!($new_type),
+                "`] instances to their original keys and back ",
+                "under a single read lock on the [`",
+                ::std::stringify!($new_type),
+                "`] registry, instead of locking it per lookup.\n\n",
+                "`f` mustn't register new [`",
+                ::std::stringify!($new_type),
+                "`] instances."
+            )]
+            #[inline]
+            #[allow(dead_code)]
+            $vis fn with_keys_cached<R>(
+                f: impl ::std::ops::FnOnce(&$crate::type_info_containers::key_wrapper::KeyCache<'_, Self>) -> R,
+            ) -> R {
+                $crate::type_info_containers::key_wrapper::with_key_cache(f)
+            }
+
+            #[doc = ::std::concat!(
+                "Calls `f` with the original key corresponding to the [`",
+                ::std::stringify!($new_type),
+                "`] instance and returns its result.\n\n",
+                "The key is borrowed under a read lock on the [`",
+                ::std::stringify!($new_type),
+                "`] registry, so `f` mustn't register new [`",
+                ::std::stringify!($new_type),
+                "`] instances."
+            )]
+            #[inline]
+            #[allow(dead_code)]
+            $vis fn with_key<R>(self, f: impl FnOnce(&$orig_type) -> R) -> R {
+                use ::std::borrow::Borrow;
+                use $crate::traits::{BlazeMapIdStatic, KeyByOffsetProvider, TypeInfoContainer};
+                let guard = Self::static_container().key_by_offset_provider();
+                let key = unsafe { guard.key_by_offset_unchecked(self.0.into_offset()) };
+                f(key.borrow())
             }
         }
 
@@ -130,7 +321,7 @@ macro_rules! key_wrapper_inner {
 
             #[inline]
             unsafe fn from_offset_unchecked(offset: usize) -> Self {
-                Self($crate::utils::OffsetProvider::<usize>::new(offset))
+                Self($crate::utils::OffsetProvider::<$offset_type>::new(offset))
             }
         }
 
@@ -143,7 +334,9 @@ macro_rules! key_wrapper_inner {
                 use $crate::sync::RwLock;
                 use $crate::type_info_containers::key_wrapper::StaticContainer;
 
-                static MAP: RwLock<StaticContainer<$orig_type>> = RwLock::new(StaticContainer::new());
+                static MAP: RwLock<StaticContainer<$orig_type>> = RwLock::new(
+                    StaticContainer::with_max_offset($crate::utils::OffsetProvider::<$offset_type>::MAX_OFFSET),
+                );
                 &MAP
             }
         }
@@ -166,7 +359,7 @@ macro_rules! key_wrapper_derive {
         impl Default for $new_type {
             #[inline]
             fn default() -> Self {
-                Self::new(Default::default())
+                Self::new(<<Self as $crate::prelude::BlazeMapId>::OrigType as Default>::default())
             }
         }
     };
@@ -223,16 +416,26 @@ macro_rules! key_wrapper_derive {
             #[inline]
             fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
                 use ::std::borrow::Borrow;
-                use $crate::traits::{KeyByOffsetProvider, TypeInfoContainer};
+                use $crate::traits::{
+                    CapacityInfoProvider, KeyByOffsetProvider, TypeInfoContainer,
+                };
 
                 let mut f = f.debug_struct(::std::stringify!($new_type));
                 let offset = self.0.into_offset();
-                let guard = <Self as $crate::prelude::BlazeMapIdStatic>::static_container()
-                    .key_by_offset_provider();
-                let original_key = unsafe { guard.key_by_offset_unchecked(offset) };
-                f.field("original_key", original_key.borrow());
-                drop(original_key);
-                drop(guard);
+                let static_container =
+                    <Self as $crate::prelude::BlazeMapIdStatic>::static_container();
+                // Registries never shrink, so the offset checked here stays valid below.
+                let num_registered = static_container.capacity_info_provider().offset_capacity();
+                if offset < num_registered {
+                    let guard = static_container.key_by_offset_provider();
+                    let original_key = unsafe { guard.key_by_offset_unchecked(offset) };
+                    f.field("original_key", original_key.borrow());
+                } else {
+                    f.field(
+                        "original_key",
+                        &::std::format_args!("<unregistered offset {offset}>"),
+                    );
+                }
                 f.field("offset", &offset).finish()
             }
         }
@@ -251,6 +454,44 @@ macro_rules! key_wrapper_derive {
             }
         }
     };
+    (@DERIVE Hash $new_type:ident) => {
+        impl ::std::hash::Hash for $new_type {
+            #[inline]
+            fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+                use ::std::borrow::Borrow;
+                use $crate::traits::{KeyByOffsetProvider, TypeInfoContainer};
+
+                let guard = <Self as $crate::prelude::BlazeMapIdStatic>::static_container()
+                    .key_by_offset_provider();
+                let original_key = unsafe { guard.key_by_offset_unchecked(self.0.into_offset()) };
+                original_key.borrow().hash(state);
+            }
+        }
+    };
+    (@DERIVE PartialEq $new_type:ident) => {
+        impl PartialEq for $new_type {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                use ::std::borrow::Borrow;
+                use $crate::traits::{KeyByOffsetProvider, TypeInfoContainer};
+
+                let Self(lhs) = self;
+                let Self(rhs) = other;
+                let guard = <Self as $crate::prelude::BlazeMapIdStatic>::static_container()
+                    .key_by_offset_provider();
+                let (lhs, rhs) = unsafe {
+                    (
+                        guard.key_by_offset_unchecked(lhs.into_offset()),
+                        guard.key_by_offset_unchecked(rhs.into_offset()),
+                    )
+                };
+                lhs.borrow() == rhs.borrow()
+            }
+        }
+    };
+    (@DERIVE Eq $new_type:ident) => {
+        impl Eq for $new_type {}
+    };
     (@DERIVE Deserialize $new_type:ident) => {
         impl<'de> $crate::external::serde::Deserialize<'de> for $new_type {
             #[inline]
@@ -290,6 +531,106 @@ macro_rules! key_wrapper_derive {
             }
         }
     };
+    (@DERIVE BorshDeserialize $new_type:ident) => {
+        impl $crate::external::borsh::BorshDeserialize for $new_type {
+            #[inline]
+            fn deserialize_reader<R>(reader: &mut R) -> ::std::io::Result<Self>
+            where
+                R: ::std::io::Read,
+            {
+                use $crate::traits::BlazeMapIdStatic;
+                let original_key: <Self as $crate::prelude::BlazeMapId>::OrigType =
+                    $crate::external::borsh::BorshDeserialize::deserialize_reader(reader)?;
+                Ok(unsafe {
+                    <Self as $crate::prelude::BlazeMapIdWrapper>::new(
+                        Self::static_container(),
+                        original_key,
+                    )
+                })
+            }
+        }
+    };
+    (@DERIVE Arbitrary $new_type:ident) => {
+        impl<'a> $crate::external::arbitrary::Arbitrary<'a> for $new_type
+        where
+            <Self as $crate::prelude::BlazeMapId>::OrigType:
+                $crate::external::arbitrary::Arbitrary<'a>,
+        {
+            #[inline]
+            fn arbitrary(
+                u: &mut $crate::external::arbitrary::Unstructured<'a>,
+            ) -> $crate::external::arbitrary::Result<Self> {
+                use $crate::traits::BlazeMapIdStatic;
+                let original_key: <Self as $crate::prelude::BlazeMapId>::OrigType =
+                    u.arbitrary()?;
+                Ok(unsafe {
+                    <Self as $crate::prelude::BlazeMapIdWrapper>::new(
+                        Self::static_container(),
+                        original_key,
+                    )
+                })
+            }
+        }
+    };
+    (@DERIVE BorshSerialize $new_type:ident) => {
+        impl $crate::external::borsh::BorshSerialize for $new_type {
+            #[inline]
+            fn serialize<W>(&self, writer: &mut W) -> ::std::io::Result<()>
+            where
+                W: ::std::io::Write,
+            {
+                use ::std::borrow::Borrow;
+                use $crate::traits::{KeyByOffsetProvider, TypeInfoContainer};
+
+                unsafe {
+                    $crate::external::borsh::BorshSerialize::serialize(
+                        <Self as $crate::prelude::BlazeMapIdStatic>::static_container()
+                            .key_by_offset_provider()
+                            .key_by_offset_unchecked(self.0.into_offset())
+                            .borrow(),
+                        writer,
+                    )
+                }
+            }
+        }
+    };
+}
+
+/// Derives the structural (offset-based) traits
+/// that aren't derived as for the original type.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! key_wrapper_structural_derive {
+    ($new_type:ident [$($derived:ident)*]) => {
+        $crate::key_wrapper_structural_derive! {@CHECK Hash      $new_type [$($derived)*]}
+        $crate::key_wrapper_structural_derive! {@CHECK PartialEq $new_type [$($derived)*]}
+        $crate::key_wrapper_structural_derive! {@CHECK Eq        $new_type [$($derived)*]}
+    };
+    (@CHECK Hash $new_type:ident []) => {
+        impl ::std::hash::Hash for $new_type {
+            #[inline]
+            fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+                self.0.hash(state);
+            }
+        }
+    };
+    (@CHECK PartialEq $new_type:ident []) => {
+        impl PartialEq for $new_type {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+    };
+    (@CHECK Eq $new_type:ident []) => {
+        impl Eq for $new_type {}
+    };
+    (@CHECK Hash      $new_type:ident [Hash      $($rest:ident)*]) => {};
+    (@CHECK PartialEq $new_type:ident [PartialEq $($rest:ident)*]) => {};
+    (@CHECK Eq        $new_type:ident [Eq        $($rest:ident)*]) => {};
+    (@CHECK $checked:ident $new_type:ident [$other:ident $($rest:ident)*]) => {
+        $crate::key_wrapper_structural_derive! {@CHECK $checked $new_type [$($rest)*]}
+    };
 }
 
 #[doc(hidden)]
@@ -322,4 +663,67 @@ macro_rules! assigned_offset_derive {
             }
         }
     };
+    (@DERIVE Serialize $new_type:ident) => {
+        impl $crate::external::serde::Serialize for $new_type {
+            #[inline]
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: $crate::external::serde::Serializer,
+            {
+                $crate::external::serde::Serialize::serialize(&self.0.into_offset(), serializer)
+            }
+        }
+    };
+    (@DERIVE Deserialize $new_type:ident) => {
+        impl<'de> $crate::external::serde::Deserialize<'de> for $new_type {
+            #[inline]
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: $crate::external::serde::Deserializer<'de>,
+            {
+                use $crate::external::serde::de::Error;
+                use $crate::traits::BlazeMapIdStatic;
+                let offset: usize =
+                    $crate::external::serde::Deserialize::deserialize(deserializer)?;
+                Self::try_from_offset(offset).ok_or_else(|| {
+                    D::Error::custom(::std::format_args!(
+                        "offset {offset} is not registered for {}",
+                        ::std::stringify!($new_type)
+                    ))
+                })
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! key_wrapper_convert {
+    (@CONVERT From $new_type:ident($orig_type:ty)) => {
+        impl ::std::convert::From<$orig_type> for $new_type {
+            #[inline]
+            fn from(value: $orig_type) -> Self {
+                use $crate::traits::BlazeMapIdStatic;
+                unsafe {
+                    <Self as $crate::prelude::BlazeMapIdWrapper>::new(
+                        Self::static_container(),
+                        value,
+                    )
+                }
+            }
+        }
+
+        impl ::std::convert::From<$new_type> for $orig_type {
+            #[inline]
+            fn from(value: $new_type) -> Self {
+                use ::std::borrow::Borrow;
+                use $crate::traits::{KeyByOffsetProvider, TypeInfoContainer};
+
+                let guard = <$new_type as $crate::prelude::BlazeMapIdStatic>::static_container()
+                    .key_by_offset_provider();
+                let key = unsafe { guard.key_by_offset_unchecked(value.0.into_offset()) };
+                ::std::clone::Clone::clone(key.borrow())
+            }
+        }
+    };
 }
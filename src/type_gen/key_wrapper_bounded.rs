@@ -5,6 +5,10 @@
 /// that the number of unique keys doesn't exceed `MAX_CAP`, it's optimized for
 /// read operations so that they don't create any multi-thread contention.
 ///
+/// The offsets are assigned in the same way as by
+/// [`define_key_wrapper`](crate::define_key_wrapper): contiguously from `0`,
+/// in the order of the first registration of each distinct key.
+///
 /// This macro supports optional inference of standard traits using the
 /// following syntax:
 ///
@@ -17,8 +21,19 @@
 ///     `PartialOrd`)
 ///   * `Debug`
 ///   * `Display`
+///   * `Hash` (hashes the original key instead of the offset, so that the hash
+///     matches the one of the original key)
+///   * `PartialEq` (compares the original keys instead of the offsets; since
+///     the static registry assigns exactly one offset to each original key,
+///     the result is always the same as that of the default comparison of the
+///     offsets, so this only adds the overhead of decoding the keys)
+///   * `Eq`
 ///   * `Serialize` (with `serde` feature only)
 ///   * `Deserialize` (with `serde` feature only)
+///   * `BorshSerialize` (with `borsh` feature only)
+///   * `BorshDeserialize` (with `borsh` feature only)
+///   * `Arbitrary` (with `arbitrary` feature only; registers the generated
+///     original key)
 /// * `Derive(as for usize)` — derives traits in the same way as for the serial
 ///   number assigned when registering an instance of the original type the
 ///   first time
@@ -29,6 +44,39 @@
 ///   * `PartialOrd` (mutually exclusive with `Ord`)
 ///   * `Ord` (also derives `PartialOrd`, so mutually exclusive with
 ///     `PartialOrd`)
+///   * `Serialize` (with `serde` feature only; emits the bare offset, so
+///     mutually exclusive with `Serialize` derived as for the original type)
+///   * `Deserialize` (with `serde` feature only; accepts the bare offset of an
+///     already registered instance, so mutually exclusive with `Deserialize`
+///     derived as for the original type)
+///
+///   The offsets are only meaningful within a single process, so the
+///   serialized identifiers mustn't cross the boundary of the registry
+///   that assigned them.
+///
+/// The optional `NicheOptimized` marker, placed right after the type
+/// `MAX_CAP` line, makes the type store its offset as a [`NonZeroUsize`]
+/// (offset + 1), so that `Option` of the type takes no extra space.
+///
+/// The optional `Repr: u32` section, placed after the `NicheOptimized`
+/// marker (if any), makes the type store its offset as a `u32`
+/// (or a [`NonZeroU32`] together with `NicheOptimized`), halving its size
+/// on 64-bit targets. Creating an instance with an offset that doesn't fit
+/// into `u32` panics.
+///
+/// [`NonZeroUsize`]: core::num::NonZeroUsize
+/// [`NonZeroU32`]: core::num::NonZeroU32
+///
+/// # Attributes
+///
+/// Outer attributes placed before the type declaration, including doc
+/// comments, are applied to the generated struct ahead of its built-in
+/// `#[derive(Clone, Copy)]`. Besides `Clone` and `Copy`, the macro always implements
+/// `PartialEq`, `Eq` and `Hash` (by offset, unless derived as for the original
+/// type), so these names are reserved along with the ones supported by the
+/// `Derive` sections, which should be used instead.
+/// As with [`define_plain_id`](crate::define_plain_id), `#[cfg(...)]` belongs
+/// on the macro invocation itself.
 ///
 /// # Example
 ///
@@ -60,11 +108,57 @@
 /// ```
 #[macro_export]
 macro_rules! define_key_wrapper_bounded {
+    (
+        $(#[$attrs:meta])*
+        $vis:vis
+        struct $new_type:ident($orig_type:ty);
+        MAX_CAP = $capacity:literal;
+        NicheOptimized
+        $(; Repr: $repr:ident)?
+        $(; Derive(as for Original Type): {$($to_derive_orig:ident),+ $(,)?} )?
+        $(; Derive(as for usize):         {$(  $to_derive_sn:ident),+ $(,)?} )?
+        $(;)?
+    ) => {
+        $crate::key_wrapper_bounded_inner! {
+            $(#[$attrs])*
+            $vis
+            struct $new_type($orig_type);
+            MAX_CAP = $capacity;
+            OFFSET = $crate::offset_repr!(NicheOptimized $($repr)?)
+        }
+        $crate::key_wrapper_structural_derive! {$new_type [$($($to_derive_orig)*)?]}
+        $($($crate::key_wrapper_derive!     {@DERIVE $to_derive_orig $new_type})*)?
+        $($($crate::assigned_offset_derive! {@DERIVE   $to_derive_sn $new_type})*)?
+
+    };
+    (
+        $(#[$attrs:meta])*
+        $vis:vis
+        struct $new_type:ident($orig_type:ty);
+        MAX_CAP = $capacity:literal;
+        NicheOptimized
+        $(; Repr: $repr:ident)?
+        $(; Derive(as for usize):         {$(  $to_derive_sn:ident),+ $(,)?} )?
+        $(; Derive(as for Original Type): {$($to_derive_orig:ident),+ $(,)?} )?
+        $(;)?
+    ) => {
+        $crate::key_wrapper_bounded_inner! {
+            $(#[$attrs])*
+            $vis
+            struct $new_type($orig_type);
+            MAX_CAP = $capacity;
+            OFFSET = $crate::offset_repr!(NicheOptimized $($repr)?)
+        }
+        $crate::key_wrapper_structural_derive! {$new_type [$($($to_derive_orig)*)?]}
+        $($($crate::key_wrapper_derive!     {@DERIVE $to_derive_orig $new_type})*)?
+        $($($crate::assigned_offset_derive! {@DERIVE   $to_derive_sn $new_type})*)?
+    };
     (
         $(#[$attrs:meta])*
         $vis:vis
         struct $new_type:ident($orig_type:ty);
         MAX_CAP = $capacity:literal
+        $(; Repr: $repr:ident)?
         $(; Derive(as for Original Type): {$($to_derive_orig:ident),+ $(,)?} )?
         $(; Derive(as for usize):         {$(  $to_derive_sn:ident),+ $(,)?} )?
         $(;)?
@@ -73,16 +167,20 @@ macro_rules! define_key_wrapper_bounded {
             $(#[$attrs])*
             $vis
             struct $new_type($orig_type);
-            MAX_CAP = $capacity
+            MAX_CAP = $capacity;
+            OFFSET = $crate::offset_repr!($($repr)?)
         }
+        $crate::key_wrapper_structural_derive! {$new_type [$($($to_derive_orig)*)?]}
         $($($crate::key_wrapper_derive!     {@DERIVE $to_derive_orig $new_type})*)?
         $($($crate::assigned_offset_derive! {@DERIVE   $to_derive_sn $new_type})*)?
+
     };
     (
         $(#[$attrs:meta])*
         $vis:vis
         struct $new_type:ident($orig_type:ty);
         MAX_CAP = $capacity:literal
+        $(; Repr: $repr:ident)?
         $(; Derive(as for usize):         {$(  $to_derive_sn:ident),+ $(,)?} )?
         $(; Derive(as for Original Type): {$($to_derive_orig:ident),+ $(,)?} )?
         $(;)?
@@ -91,8 +189,10 @@ macro_rules! define_key_wrapper_bounded {
             $(#[$attrs])*
             $vis
             struct $new_type($orig_type);
-            MAX_CAP = $capacity
+            MAX_CAP = $capacity;
+            OFFSET = $crate::offset_repr!($($repr)?)
         }
+        $crate::key_wrapper_structural_derive! {$new_type [$($($to_derive_orig)*)?]}
         $($($crate::key_wrapper_derive!     {@DERIVE $to_derive_orig $new_type})*)?
         $($($crate::assigned_offset_derive! {@DERIVE   $to_derive_sn $new_type})*)?
     }
@@ -105,21 +205,58 @@ macro_rules! key_wrapper_bounded_inner {
         $(#[$attrs:meta])*
         $vis:vis
         struct $new_type:ident($orig_type:ty);
-        MAX_CAP = $capacity:literal
+        MAX_CAP = $capacity:literal;
+        OFFSET = $offset_type:ty
     ) => {
         $(#[$attrs])*
-        #[derive(Clone, Copy, Eq, PartialEq, Hash)]
+        #[derive(Clone, Copy)]
         #[repr(transparent)]
-        $vis struct $new_type($crate::utils::OffsetProvider<usize>);
+        $vis struct $new_type($crate::utils::OffsetProvider<$offset_type>);
 
         #[cfg(not(feature = "loom"))]
         impl $new_type
         {
             #[doc = ::std::concat!("Creates a new instance of [`", ::std::stringify!($new_type), "`].")]
             #[inline]
-            $vis fn new(value: $orig_type) -> Self {
+            $vis fn new(value: impl ::std::convert::Into<$orig_type>) -> Self {
                 use $crate::traits::BlazeMapIdStatic;
-                unsafe { <Self as $crate::prelude::BlazeMapIdWrapper>::new(Self::static_container(), value) }
+                unsafe { <Self as $crate::prelude::BlazeMapIdWrapper>::new(Self::static_container(), value.into()) }
+            }
+
+            #[doc = ::std::concat!(
+                "Creates a new instance of [`",
+                ::std::stringify!($new_type),
+                "`].\n\n",
+                "# Errors\n",
+                "Returns a `CapacityOverflow` error instead of panicking ",
+                "if the registration of a new key would exceed the maximum capacity."
+            )]
+            #[inline]
+            #[allow(dead_code)]
+            $vis fn try_new(value: impl ::std::convert::Into<$orig_type>) -> ::std::result::Result<Self, $crate::error::CapacityOverflow> {
+                use $crate::traits::BlazeMapIdStatic;
+                unsafe { <Self as $crate::prelude::BlazeMapIdWrapper>::try_new(Self::static_container(), value.into()) }
+            }
+
+            #[doc = ::std::concat!(
+                "Returns the [`",
+                ::std::stringify!($new_type),
+                "`] instance corresponding to the given key if it is already registered, ",
+                "and `None` otherwise.\n\n",
+                "Unlike [`",
+                ::std::stringify!($new_type),
+                "::new`], never registers the key, and accepts any borrowed form of the original type, ",
+                "so looking up an interned `Vec<u8>` by a `&[u8]` doesn't allocate."
+            )]
+            #[inline]
+            #[allow(dead_code)]
+            $vis fn get_existing<Q>(key: &Q) -> ::std::option::Option<Self>
+            where
+                $orig_type: ::std::borrow::Borrow<Q>,
+                Q: ::std::hash::Hash + ::std::cmp::Eq + ?::std::marker::Sized,
+            {
+                use $crate::traits::{BlazeMapIdStatic, WrapKey};
+                Self::static_container().find_key(key)
             }
 
             #[doc = ::std::concat!(
@@ -134,6 +271,45 @@ macro_rules! key_wrapper_bounded_inner {
                 let static_container = <Self as $crate::traits::BlazeMapIdStatic>::static_container();
                 unsafe { static_container.key_by_offset_unchecked(self.0.into_offset()) }
             }
+
+            #[doc = ::std::concat!(
+                "Returns the number of [`",
+                ::std::stringify!($new_type),
+                "`] keys registered so far, i.e. the number of pre-allocated key slots actually in use."
+            )]
+            #[inline]
+            #[must_use]
+            #[allow(dead_code)]
+            $vis fn used_capacity() -> usize {
+                <Self as $crate::traits::BlazeMapIdStatic>::static_container().used_capacity()
+            }
+
+            #[doc = ::std::concat!(
+                "Returns the maximum number of [`",
+                ::std::stringify!($new_type),
+                "`] keys that can be registered, i.e. `MAX_CAP`.\n\n",
+                "Comparing it with [`",
+                ::std::stringify!($new_type),
+                "::used_capacity`] allows detecting a grossly over-provisioned `MAX_CAP`."
+            )]
+            #[inline]
+            #[must_use]
+            #[allow(dead_code)]
+            $vis const fn max_capacity() -> usize {
+                $capacity
+            }
+
+            #[doc = ::std::concat!(
+                "Returns an iterator over the offsets and the original keys of all the [`",
+                ::std::stringify!($new_type),
+                "`] keys registered so far, in ascending offset order.\n\n",
+                "Doesn't lock anything. Keys registered after the iterator is created aren't visited."
+            )]
+            #[inline]
+            #[allow(dead_code)]
+            $vis fn registered_keys() -> impl ::std::iter::ExactSizeIterator<Item = (usize, &'static $orig_type)> {
+                <Self as $crate::traits::BlazeMapIdStatic>::static_container().registered_keys()
+            }
         }
 
         impl $crate::prelude::BlazeMapId for $new_type
@@ -148,7 +324,7 @@ macro_rules! key_wrapper_bounded_inner {
 
             #[inline]
             unsafe fn from_offset_unchecked(offset: usize) -> Self {
-                Self($crate::utils::OffsetProvider::<usize>::new(offset))
+                Self($crate::utils::OffsetProvider::<$offset_type>::new(offset))
             }
         }
 
@@ -160,6 +336,7 @@ macro_rules! key_wrapper_bounded_inner {
             {
                 use $crate::type_info_containers::key_wrapper_bounded::StaticContainer;
                 use $crate::external::once_cell::sync::Lazy;
+                #[allow(clippy::non_std_lazy_statics)]
                 static MAP: Lazy<StaticContainer<$orig_type, $capacity>> = Lazy::new(StaticContainer::new);
                 &MAP
             }
@@ -172,6 +349,15 @@ macro_rules! key_wrapper_bounded_inner {
                 use $crate::traits::WrapKey;
                 type_info_container.wrap_key(key)
             }
+
+            #[inline]
+            unsafe fn try_new(
+                type_info_container: &Self::TypeInfoContainer,
+                key: $orig_type,
+            ) -> Result<Self, $crate::error::CapacityOverflow> {
+                use $crate::traits::WrapKey;
+                type_info_container.try_wrap_key(key)
+            }
         }
     }
 }
@@ -14,6 +14,40 @@
 ///   * `Ord` (also derives `PartialOrd`, so mutually exclusive with
 ///     `PartialOrd`)
 ///   * `Serialize` (with `serde` feature only)
+///   * `BorshSerialize` (with `borsh` feature only)
+///   * `Arbitrary` (with `arbitrary` feature only; picks one of the instances
+///     created so far)
+///
+/// The optional `NicheOptimized` marker, placed right after the type
+/// declaration, makes the type store its offset as a [`NonZeroUsize`]
+/// (offset + 1), so that `Option` of the type takes no extra space.
+///
+/// The optional `Repr: u32` section, placed after the `NicheOptimized`
+/// marker (if any), makes the type store its offset as a `u32`
+/// (or a [`NonZeroU32`] together with `NicheOptimized`), halving its size
+/// on 64-bit targets. Creating an instance with an offset that doesn't fit
+/// into `u32` panics.
+///
+/// The optional `StartFrom` section, placed after the type declaration
+/// (and the `NicheOptimized` marker and the `Repr` section, if any), sets the offset of the first
+/// generated instance, so that the lower offsets can be reserved,
+/// e.g. for sentinel values.
+///
+/// [`NonZeroUsize`]: core::num::NonZeroUsize
+/// [`NonZeroU32`]: core::num::NonZeroU32
+///
+/// # Attributes
+///
+/// Outer attributes placed before the type declaration, including doc
+/// comments, are applied to the generated struct ahead of its built-in
+/// `#[derive(Clone, Copy, Eq, PartialEq, Hash)]`. These traits, as well as
+/// `Debug` and `Display`, are implemented by the macro itself, so they are
+/// reserved and mustn't be derived through the attributes;
+/// use the `Derive` section for the ordering traits.
+///
+/// Put `#[cfg(...)]` on the macro invocation itself rather than inside it,
+/// since an attribute inside only applies to the struct
+/// and not to the generated `impl` blocks.
 ///
 /// # Example
 ///
@@ -40,17 +74,40 @@
 /// ```
 #[macro_export]
 macro_rules! define_plain_id {
+    (
+        $(#[$attrs:meta])*
+        $vis:vis
+        struct $new_type:ident;
+        NicheOptimized
+        $(; Repr: $repr:ident)?
+        $(; StartFrom: $first_offset:expr)?
+        $(; Derive: {$($to_derive_sn:ident),+ $(,)?} )?
+        $(;)?
+    ) => {
+        $crate::plain_id_inner! {
+            $(#[$attrs])*
+            $vis
+            struct $new_type;
+            OFFSET = $crate::offset_repr!(NicheOptimized $($repr)?);
+            FIRST_OFFSET = $crate::plain_id_inner!(@FIRST_OFFSET $($first_offset)?)
+        }
+        $($($crate::plain_id_derive! {@DERIVE $to_derive_sn $new_type})*)?
+    };
     (
         $(#[$attrs:meta])*
         $vis:vis
         struct $new_type:ident
+        $(; Repr: $repr:ident)?
+        $(; StartFrom: $first_offset:expr)?
         $(; Derive: {$($to_derive_sn:ident),+ $(,)?} )?
         $(;)?
     ) => {
         $crate::plain_id_inner! {
             $(#[$attrs])*
             $vis
-            struct $new_type
+            struct $new_type;
+            OFFSET = $crate::offset_repr!($($repr)?);
+            FIRST_OFFSET = $crate::plain_id_inner!(@FIRST_OFFSET $($first_offset)?)
         }
         $($($crate::plain_id_derive! {@DERIVE $to_derive_sn $new_type})*)?
     };
@@ -59,32 +116,82 @@ macro_rules! define_plain_id {
 #[doc(hidden)]
 #[macro_export]
 macro_rules! plain_id_inner {
+    (@FIRST_OFFSET) => {
+        0
+    };
+    (@FIRST_OFFSET $first_offset:expr) => {
+        $first_offset
+    };
     (
         $(#[$attrs:meta])*
         $vis:vis
-        struct $new_type:ident
+        struct $new_type:ident;
+        OFFSET = $offset_type:ty;
+        FIRST_OFFSET = $first_offset:expr
     ) => {
         $(#[$attrs])*
         #[derive(Clone, Copy, Eq, PartialEq, Hash)]
         #[repr(transparent)]
-        $vis struct $new_type($crate::utils::OffsetProvider<usize>);
+        $vis struct $new_type($crate::utils::OffsetProvider<$offset_type>);
 
         impl $new_type
         {
-            #[doc = ::std::concat!("Creates a new instance of [`", ::std::stringify!($new_type), "`].")]
+            #[doc = ::core::concat!("Creates a new instance of [`", ::core::stringify!($new_type), "`].")]
             #[inline]
             #[cfg(not(feature = "loom"))]
             $vis fn new() -> Self {
                 let next_id = <Self as $crate::prelude::BlazeMapIdStatic>::static_container().next_id();
-                Self(unsafe { $crate::utils::OffsetProvider::<usize>::new(next_id) })
+                Self(unsafe { $crate::utils::OffsetProvider::<$offset_type>::new(next_id) })
             }
 
-            #[doc = ::std::concat!("Creates a new instance of [`", ::std::stringify!($new_type), "`].")]
+            #[doc = ::core::concat!("Creates a new instance of [`", ::core::stringify!($new_type), "`].")]
             #[inline]
             #[cfg(feature = "loom")]
             $vis fn new(type_info_container: &<Self as $crate::prelude::BlazeMapId>::TypeInfoContainer) -> Self {
                 let next_id = type_info_container.next_id();
-                Self(unsafe { $crate::utils::OffsetProvider::<usize>::new(next_id) })
+                Self(unsafe { $crate::utils::OffsetProvider::<$offset_type>::new(next_id) })
+            }
+
+            #[doc = ::core::concat!(
+                "Creates a new instance of [`", ::core::stringify!($new_type), "`], ",
+                "or returns `None` instead of panicking if its offset overflows the offset type."
+            )]
+            #[inline]
+            #[allow(dead_code)]
+            #[cfg(not(feature = "loom"))]
+            $vis fn try_new() -> ::core::option::Option<Self> {
+                let next_id = <Self as $crate::prelude::BlazeMapIdStatic>::static_container().try_next_id()?;
+                unsafe { $crate::utils::OffsetProvider::<$offset_type>::try_new(next_id) }.map(Self)
+            }
+
+            #[doc = ::core::concat!(
+                "Creates a new instance of [`", ::core::stringify!($new_type), "`], ",
+                "or returns `None` instead of panicking if its offset overflows the offset type."
+            )]
+            #[inline]
+            #[allow(dead_code)]
+            #[cfg(feature = "loom")]
+            $vis fn try_new(type_info_container: &<Self as $crate::prelude::BlazeMapId>::TypeInfoContainer) -> ::core::option::Option<Self> {
+                let next_id = type_info_container.try_next_id()?;
+                unsafe { $crate::utils::OffsetProvider::<$offset_type>::try_new(next_id) }.map(Self)
+            }
+
+            #[doc = ::core::concat!("Creates `n` new instances of [`", ::core::stringify!($new_type), "`] with consecutive offsets.")]
+            #[inline]
+            #[cfg(not(feature = "loom"))]
+            $vis fn new_n(n: usize) -> impl ::core::iter::ExactSizeIterator<Item = Self> {
+                <Self as $crate::prelude::BlazeMapIdStatic>::static_container()
+                    .next_ids(n)
+                    .map(|next_id| Self(unsafe { $crate::utils::OffsetProvider::<$offset_type>::new(next_id) }))
+            }
+
+            #[doc = ::core::concat!("Creates `n` new instances of [`", ::core::stringify!($new_type), "`] with consecutive offsets.")]
+            #[inline]
+            #[cfg(feature = "loom")]
+            $vis fn new_n(type_info_container: &<Self as $crate::prelude::BlazeMapId>::TypeInfoContainer, n: usize) -> impl ::core::iter::ExactSizeIterator<Item = Self> {
+                type_info_container
+                    .next_ids(n)
+                    .map(|next_id| Self(unsafe { $crate::utils::OffsetProvider::<$offset_type>::new(next_id) }))
             }
         }
 
@@ -100,7 +207,7 @@ macro_rules! plain_id_inner {
 
             #[inline]
             unsafe fn from_offset_unchecked(offset: usize) -> Self {
-                Self($crate::utils::OffsetProvider::<usize>::new(offset))
+                Self($crate::utils::OffsetProvider::<$offset_type>::new(offset))
             }
         }
 
@@ -111,26 +218,27 @@ macro_rules! plain_id_inner {
             fn static_container() -> &'static Self::TypeInfoContainer
             {
                 use $crate::type_info_containers::plain_id::StaticContainer;
-                static INFO: StaticContainer = StaticContainer::new();
+                static INFO: StaticContainer = StaticContainer::with_first_offset($first_offset)
+                    .with_max_offset($crate::utils::OffsetProvider::<$offset_type>::MAX_OFFSET);
                 &INFO
             }
         }
 
-        impl ::std::fmt::Debug for $new_type
+        impl ::core::fmt::Debug for $new_type
         {
             #[inline]
-            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result
             {
-                f.debug_tuple(::std::stringify!($new_type))
+                f.debug_tuple(::core::stringify!($new_type))
                     .field(&self.0.into_offset())
                     .finish()
             }
         }
 
-        impl ::std::fmt::Display for $new_type
+        impl ::core::fmt::Display for $new_type
         {
             #[inline]
-            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result
             {
                 write!(f, "{}", self.0.into_offset())
             }
@@ -144,7 +252,7 @@ macro_rules! plain_id_derive {
     (@DERIVE PartialOrd $new_type:ident) => {
         impl PartialOrd for $new_type {
             #[inline]
-            fn partial_cmp(&self, other: &Self) -> Option<::std::cmp::Ordering> {
+            fn partial_cmp(&self, other: &Self) -> Option<::core::cmp::Ordering> {
                 let Self(lhs) = self;
                 let Self(rhs) = other;
                 lhs.into_offset().partial_cmp(&rhs.into_offset())
@@ -154,14 +262,14 @@ macro_rules! plain_id_derive {
     (@DERIVE Ord $new_type:ident) => {
         impl PartialOrd for $new_type {
             #[inline]
-            fn partial_cmp(&self, other: &Self) -> Option<::std::cmp::Ordering> {
+            fn partial_cmp(&self, other: &Self) -> Option<::core::cmp::Ordering> {
                 Some(self.cmp(other))
             }
         }
 
         impl Ord for $new_type {
             #[inline]
-            fn cmp(&self, other: &Self) -> ::std::cmp::Ordering {
+            fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
                 let Self(lhs) = self;
                 let Self(rhs) = other;
                 lhs.into_offset().cmp(&rhs.into_offset())
@@ -179,4 +287,33 @@ macro_rules! plain_id_derive {
             }
         }
     };
+    (@DERIVE Arbitrary $new_type:ident) => {
+        impl<'a> $crate::external::arbitrary::Arbitrary<'a> for $new_type {
+            #[inline]
+            fn arbitrary(
+                u: &mut $crate::external::arbitrary::Unstructured<'a>,
+            ) -> $crate::external::arbitrary::Result<Self> {
+                use $crate::traits::{BlazeMapIdStatic, CapacityInfoProvider, TypeInfoContainer};
+                let capacity_info = Self::static_container().capacity_info_provider();
+                let first_offset = capacity_info.first_offset();
+                let index = u.choose_index(capacity_info.offset_capacity() - first_offset)?;
+                Ok(unsafe {
+                    <Self as $crate::prelude::BlazeMapId>::from_offset_unchecked(
+                        first_offset + index,
+                    )
+                })
+            }
+        }
+    };
+    (@DERIVE BorshSerialize $new_type:ident) => {
+        impl $crate::external::borsh::BorshSerialize for $new_type {
+            #[inline]
+            fn serialize<W>(&self, writer: &mut W) -> ::std::io::Result<()>
+            where
+                W: ::std::io::Write,
+            {
+                $crate::external::borsh::BorshSerialize::serialize(&self.0.into_offset(), writer)
+            }
+        }
+    };
 }
@@ -1,3 +1,5 @@
+#[cfg(feature = "std")]
 pub mod key_wrapper;
+#[cfg(feature = "std")]
 pub mod key_wrapper_bounded;
 pub mod plain_id;
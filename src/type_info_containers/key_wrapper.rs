@@ -1,3 +1,5 @@
+#[cfg(not(feature = "loom"))]
+use crate::prelude::BlazeMapIdStatic;
 use crate::{
     prelude::BlazeMapId,
     sync::RwLock,
@@ -9,6 +11,7 @@ use std::{
     borrow::Borrow,
     collections::{hash_map::Entry, HashMap},
     hash::Hash,
+    marker::PhantomData,
     ops::Deref,
 };
 
@@ -20,6 +23,7 @@ use std::{
 pub struct StaticContainer<K> {
     offset_to_orig: Vec<K>,
     orig_to_offset: Lazy<HashMap<K, usize>>,
+    offset_limit: usize,
 }
 
 /// Loom-testable version of the above container.
@@ -32,6 +36,7 @@ pub struct StaticContainer<K> {
 pub struct StaticContainer<K> {
     offset_to_orig: Vec<K>,
     orig_to_offset: HashMap<K, usize>,
+    offset_limit: usize,
 }
 
 impl<K> Default for StaticContainer<K> {
@@ -47,9 +52,20 @@ impl<K> StaticContainer<K> {
     #[must_use]
     #[cfg(not(feature = "loom"))]
     pub const fn new() -> Self {
+        Self::with_max_offset(usize::MAX)
+    }
+
+    /// Creates a new instance of [`StaticContainer`]
+    /// that assigns offsets up to `max_offset`, e.g. up to the highest
+    /// offset that the offset representation of the identifier type can hold.
+    #[inline]
+    #[must_use]
+    #[cfg(not(feature = "loom"))]
+    pub const fn with_max_offset(max_offset: usize) -> Self {
         Self {
             offset_to_orig: vec![],
             orig_to_offset: Lazy::new(Default::default),
+            offset_limit: max_offset,
         }
     }
 
@@ -67,6 +83,7 @@ impl<K> StaticContainer<K> {
         Self {
             offset_to_orig: vec![],
             orig_to_offset: HashMap::new(),
+            offset_limit: usize::MAX,
         }
     }
 }
@@ -94,6 +111,7 @@ where
                 let offset = match container.orig_to_offset.entry(key) {
                     Entry::Vacant(entry) => {
                         let offset = container.offset_to_orig.len();
+                        assert!(offset <= container.offset_limit, "offset overflow");
                         container.offset_to_orig.push(entry.key().clone());
                         entry.insert(offset);
                         offset
@@ -105,6 +123,48 @@ where
             }
         }
     }
+
+    #[inline]
+    fn find_key<Q>(&self, key: &Q) -> Option<I>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        #[cfg(not(feature = "loom"))]
+        let offset = self.read().orig_to_offset.get(key).copied();
+        #[cfg(feature = "loom")]
+        let offset = self.read().unwrap().orig_to_offset.get(key).copied();
+        offset.map(|offset| unsafe { I::from_offset_unchecked(offset) })
+    }
+
+    #[inline]
+    fn wrap_keys(&self, keys: impl IntoIterator<Item = K>) -> Vec<I> {
+        // Collect the keys before locking, since the iterator may run user code.
+        let keys: Vec<K> = keys.into_iter().collect();
+        #[cfg(not(feature = "loom"))]
+        let mut guard = self.write();
+        #[cfg(feature = "loom")]
+        let mut guard = self.write().unwrap();
+        let container = &mut *guard;
+        let ids = keys
+            .into_iter()
+            .map(|key| {
+                let offset = match container.orig_to_offset.entry(key) {
+                    Entry::Vacant(entry) => {
+                        let offset = container.offset_to_orig.len();
+                        assert!(offset <= container.offset_limit, "offset overflow");
+                        container.offset_to_orig.push(entry.key().clone());
+                        entry.insert(offset);
+                        offset
+                    }
+                    Entry::Occupied(entry) => *entry.get(),
+                };
+                unsafe { I::from_offset_unchecked(offset) }
+            })
+            .collect();
+        drop(guard);
+        ids
+    }
 }
 
 impl<K> TypeInfoContainer for RwLock<StaticContainer<K>>
@@ -132,6 +192,14 @@ where
         let result = self.read().unwrap();
         result
     }
+
+    #[inline]
+    #[cfg(all(feature = "test-reset", not(feature = "loom")))]
+    unsafe fn reset(&self) {
+        let mut guard = self.write();
+        guard.offset_to_orig.clear();
+        guard.orig_to_offset.clear();
+    }
 }
 
 impl<K> CapacityInfoProvider for StaticContainer<K> {
@@ -151,3 +219,77 @@ impl<K> KeyByOffsetProvider<K> for StaticContainer<K> {
         result
     }
 }
+
+/// A borrowed view of the registry of a key-wrapper type `I`
+/// that resolves any number of lookups under a single read lock.
+///
+/// Created by [`with_key_cache`].
+#[derive(Debug)]
+pub struct KeyCache<'a, I>
+where
+    I: BlazeMapId,
+{
+    container: &'a StaticContainer<I::OrigType>,
+    phantom: PhantomData<I>,
+}
+
+impl<I> KeyCache<'_, I>
+where
+    I: BlazeMapId,
+{
+    /// Returns the original key corresponding to the given identifier.
+    #[inline]
+    #[must_use]
+    pub fn key(&self, id: I) -> &I::OrigType {
+        #[cfg(not(feature = "loom"))]
+        let result = unsafe { self.container.offset_to_orig.get_unchecked(id.get_offset()) };
+        #[cfg(feature = "loom")]
+        let result = self.container.offset_to_orig.get(id.get_offset()).unwrap();
+        result
+    }
+
+    /// Returns the identifier corresponding to the given key
+    /// if it is already registered.
+    #[inline]
+    #[must_use]
+    pub fn get<Q>(&self, key: &Q) -> Option<I>
+    where
+        I::OrigType: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let offset = self.container.orig_to_offset.get(key).copied()?;
+        Some(unsafe { I::from_offset_unchecked(offset) })
+    }
+
+    /// Returns the number of registered keys.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.container.offset_to_orig.len()
+    }
+
+    /// Returns `true` if no keys are registered.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.container.offset_to_orig.is_empty()
+    }
+}
+
+/// Takes a single read lock on the registry of `I`
+/// and calls `f` with a [`KeyCache`] borrowing it.
+///
+/// `f` mustn't register new `I` instances, since the registry is locked
+/// for reading until `f` returns.
+#[cfg(not(feature = "loom"))]
+#[inline]
+pub fn with_key_cache<I, R>(f: impl FnOnce(&KeyCache<'_, I>) -> R) -> R
+where
+    I: BlazeMapIdStatic<TypeInfoContainer = RwLock<StaticContainer<<I as BlazeMapId>::OrigType>>>,
+{
+    let guard = I::static_container().read();
+    f(&KeyCache {
+        container: &guard,
+        phantom: PhantomData,
+    })
+}
@@ -1,6 +1,7 @@
 #[cfg(feature = "loom")]
 use crate::sync::RwLockReadGuard;
 use crate::{
+    error::CapacityOverflow,
     prelude::BlazeMapId,
     sync::{AtomicUsize, Ordering, RwLock},
     traits::{CapacityInfoProvider, KeyByOffsetProvider, TypeInfoContainer, WrapKey},
@@ -98,6 +99,41 @@ impl<K, const CAP: usize> StaticContainer<K, CAP> {
         }
     }
 
+    /// Returns the number of keys registered so far,
+    /// i.e. the number of pre-allocated key slots actually in use.
+    #[inline]
+    #[must_use]
+    pub fn used_capacity(&self) -> usize {
+        self.next_offset.load(Ordering::Acquire)
+    }
+
+    /// Returns the maximum number of keys that can be registered,
+    /// i.e. the number of pre-allocated key slots.
+    ///
+    /// Comparing it with [`used_capacity`](Self::used_capacity) allows
+    /// detecting a grossly over-provisioned `CAP`.
+    #[inline]
+    #[must_use]
+    pub const fn max_capacity(&self) -> usize {
+        CAP
+    }
+
+    /// Returns an iterator over the offsets and the original keys
+    /// of all the keys registered so far, in ascending offset order.
+    ///
+    /// Keys registered after the iterator is created aren't visited.
+    #[inline]
+    #[cfg(not(feature = "loom"))]
+    pub fn registered_keys(&self) -> impl ExactSizeIterator<Item = (usize, &K)> {
+        let num_init = self.next_offset.load(Ordering::Acquire);
+        // Every slot below `next_offset` has been initialized before
+        // `next_offset` was advanced past it, and is never written again.
+        self.offset_to_orig[..num_init]
+            .iter()
+            .enumerate()
+            .map(|(offset, cell)| (offset, unsafe { (*cell.get()).assume_init_ref() }))
+    }
+
     #[inline]
     #[doc(hidden)]
     #[cfg(not(feature = "loom"))]
@@ -120,13 +156,31 @@ where
 {
     #[inline]
     fn wrap_key(&self, key: K) -> I {
+        self.try_wrap_key(key).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    #[inline]
+    fn find_key<Q>(&self, key: &Q) -> Option<I>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        #[cfg(not(feature = "loom"))]
+        let offset = self.orig_to_offset.read().get(key).copied();
+        #[cfg(feature = "loom")]
+        let offset = self.orig_to_offset.read().unwrap().get(key).copied();
+        offset.map(|offset| unsafe { I::from_offset_unchecked(offset) })
+    }
+
+    #[inline]
+    fn try_wrap_key(&self, key: K) -> Result<I, CapacityOverflow> {
         #[cfg(not(feature = "loom"))]
         let offset = self.orig_to_offset.read().get(&key).copied();
         #[cfg(feature = "loom")]
         let offset = self.orig_to_offset.read().unwrap().get(&key).copied();
         unsafe {
             if let Some(offset) = offset {
-                I::from_offset_unchecked(offset)
+                Ok(I::from_offset_unchecked(offset))
             } else {
                 #[cfg(not(feature = "loom"))]
                 let mut guard = self.orig_to_offset.write();
@@ -135,10 +189,9 @@ where
                 let offset = match guard.entry(key) {
                     Entry::Vacant(entry) => {
                         let offset = self.next_offset.load(Ordering::Relaxed);
-                        let cell = self
-                            .offset_to_orig
-                            .get(offset)
-                            .unwrap_or_else(|| panic!("capacity {CAP} overflow"));
+                        let Some(cell) = self.offset_to_orig.get(offset) else {
+                            return Err(CapacityOverflow { capacity: CAP });
+                        };
                         #[cfg(not(feature = "loom"))]
                         (*cell.get()).write(entry.key().clone());
                         #[cfg(feature = "loom")]
@@ -155,7 +208,7 @@ where
                     Entry::Occupied(entry) => *entry.get(),
                 };
                 drop(guard);
-                I::from_offset_unchecked(offset)
+                Ok(I::from_offset_unchecked(offset))
             }
         }
     }
@@ -201,6 +254,20 @@ impl<K: 'static, const CAP: usize> TypeInfoContainer for StaticContainer<K, CAP>
     ) -> impl Deref<Target = impl KeyByOffsetProvider<Self::OrigType>> {
         self
     }
+
+    #[inline]
+    #[cfg(all(feature = "test-reset", not(feature = "loom")))]
+    unsafe fn reset(&self) {
+        let mut guard = self.orig_to_offset.write();
+        let num_init = self.next_offset.load(Ordering::Acquire);
+        if needs_drop::<K>() {
+            for cell in &self.offset_to_orig[..num_init] {
+                (*cell.get()).assume_init_drop();
+            }
+        }
+        guard.clear();
+        self.next_offset.store(0, Ordering::Release);
+    }
 }
 
 impl<K, const CAP: usize> CapacityInfoProvider for StaticContainer<K, CAP> {
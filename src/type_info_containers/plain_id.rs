@@ -2,12 +2,17 @@ use crate::{
     sync::{AtomicUsize, Ordering},
     traits::{CapacityInfoProvider, KeyByOffsetProvider, TypeInfoContainer},
 };
-use std::{borrow::Borrow, ops::Deref};
+use core::{
+    borrow::Borrow,
+    ops::{Deref, Range},
+};
 
 /// Global, statically initialized offset generator.
 #[doc(hidden)]
 #[derive(Debug)]
 pub struct StaticContainer {
+    first_offset: usize,
+    offset_limit: usize,
     next_offset: AtomicUsize,
 }
 
@@ -24,8 +29,19 @@ impl StaticContainer {
     #[must_use]
     #[cfg(not(feature = "loom"))]
     pub const fn new() -> Self {
+        Self::with_first_offset(0)
+    }
+
+    /// Creates a new instance of [`StaticContainer`]
+    /// that starts generating offsets from `first_offset`.
+    #[inline]
+    #[must_use]
+    #[cfg(not(feature = "loom"))]
+    pub const fn with_first_offset(first_offset: usize) -> Self {
         Self {
-            next_offset: AtomicUsize::new(0),
+            first_offset,
+            offset_limit: usize::MAX - 1,
+            next_offset: AtomicUsize::new(first_offset),
         }
     }
 
@@ -40,8 +56,37 @@ impl StaticContainer {
     #[must_use]
     #[cfg(feature = "loom")]
     pub fn new() -> Self {
+        Self::with_first_offset(0)
+    }
+
+    /// Creates a new instance of [`StaticContainer`]
+    /// that starts generating offsets from `first_offset`.
+    ///
+    /// # Safety
+    /// Mustn't be used outside of loom tests,
+    /// since there is no guarantee that one
+    /// [`BlazeMapId`](crate::prelude::BlazeMapId) doesn't interact with
+    /// different containers of the same type.
+    #[inline]
+    #[must_use]
+    #[cfg(feature = "loom")]
+    pub fn with_first_offset(first_offset: usize) -> Self {
+        Self {
+            first_offset,
+            offset_limit: usize::MAX - 1,
+            next_offset: AtomicUsize::new(first_offset),
+        }
+    }
+
+    /// Limits the generated offsets to `max_offset`, e.g. to the highest
+    /// offset that the offset representation of the identifier type can hold.
+    #[inline]
+    #[must_use]
+    #[cfg(not(feature = "loom"))]
+    pub const fn with_max_offset(self, max_offset: usize) -> Self {
         Self {
-            next_offset: AtomicUsize::new(0),
+            offset_limit: max_offset,
+            ..self
         }
     }
 
@@ -49,11 +94,37 @@ impl StaticContainer {
     #[inline]
     #[must_use]
     pub fn next_id(&self) -> usize {
+        self.try_next_id().expect("offset overflow")
+    }
+
+    /// Returns the next identifier, or `None` if the offsets are exhausted.
+    /// The offsets are never advanced past the maximum one.
+    #[inline]
+    #[must_use]
+    pub fn try_next_id(&self) -> Option<usize> {
         self.next_offset
             .fetch_update(Ordering::Release, Ordering::Acquire, |next_id| {
-                next_id.checked_add(1)
+                next_id
+                    .checked_add(1)
+                    .filter(|_| next_id <= self.offset_limit)
+            })
+            .ok()
+    }
+
+    /// Reserves `n` consecutive identifiers at once
+    /// and returns the range of their offsets.
+    #[inline]
+    #[must_use]
+    pub fn next_ids(&self, n: usize) -> Range<usize> {
+        let first_id = self
+            .next_offset
+            .fetch_update(Ordering::Release, Ordering::Acquire, |next_id| {
+                next_id
+                    .checked_add(n)
+                    .filter(|&end| end == next_id || end - 1 <= self.offset_limit)
             })
-            .expect("usize overflow")
+            .expect("offset overflow");
+        first_id..first_id + n
     }
 }
 
@@ -71,6 +142,12 @@ impl TypeInfoContainer for StaticContainer {
     ) -> impl Deref<Target = impl KeyByOffsetProvider<Self::OrigType>> {
         &KeyByOffsetProviderTrivial
     }
+
+    #[inline]
+    #[cfg(all(feature = "test-reset", not(feature = "loom")))]
+    unsafe fn reset(&self) {
+        self.next_offset.store(self.first_offset, Ordering::Release);
+    }
 }
 
 impl CapacityInfoProvider for StaticContainer {
@@ -78,6 +155,11 @@ impl CapacityInfoProvider for StaticContainer {
     fn offset_capacity(&self) -> usize {
         self.next_offset.load(Ordering::Acquire)
     }
+
+    #[inline]
+    fn first_offset(&self) -> usize {
+        self.first_offset
+    }
 }
 
 /// Zero-sized type that trivially implements [`KeyByOffsetProvider`].
@@ -1,4 +1,7 @@
-use std::{hash::Hash, num::NonZeroUsize};
+use core::{
+    hash::Hash,
+    num::{NonZeroU32, NonZeroUsize},
+};
 
 /// Holds and provides the `usize` offset.
 ///
@@ -18,12 +21,21 @@ use std::{hash::Hash, num::NonZeroUsize};
 pub struct OffsetProvider<T>(T);
 
 impl OffsetProvider<usize> {
+    /// The highest offset that the provider can hold.
+    pub const MAX_OFFSET: usize = usize::MAX;
+
     #[inline]
     #[must_use]
     pub unsafe fn new(offset: usize) -> Self {
         Self(offset)
     }
 
+    #[inline]
+    #[must_use]
+    pub unsafe fn try_new(offset: usize) -> Option<Self> {
+        Some(Self(offset))
+    }
+
     #[inline]
     #[must_use]
     pub fn into_offset(self) -> usize {
@@ -32,11 +44,20 @@ impl OffsetProvider<usize> {
 }
 
 impl OffsetProvider<NonZeroUsize> {
+    /// The highest offset that the provider can hold.
+    pub const MAX_OFFSET: usize = usize::MAX - 1;
+
     #[inline]
     #[must_use]
     pub unsafe fn new(offset: usize) -> Self {
-        let inner = offset.checked_add(1).expect("usize overflow");
-        Self(unsafe { NonZeroUsize::new_unchecked(inner) })
+        unsafe { Self::try_new(offset) }.expect("usize overflow")
+    }
+
+    #[inline]
+    #[must_use]
+    pub unsafe fn try_new(offset: usize) -> Option<Self> {
+        let inner = offset.checked_add(1)?;
+        Some(Self(unsafe { NonZeroUsize::new_unchecked(inner) }))
     }
 
     #[inline]
@@ -46,11 +67,83 @@ impl OffsetProvider<NonZeroUsize> {
     }
 }
 
+impl OffsetProvider<u32> {
+    /// The highest offset that the provider can hold.
+    pub const MAX_OFFSET: usize = u32::MAX as usize;
+
+    #[inline]
+    #[must_use]
+    pub unsafe fn new(offset: usize) -> Self {
+        unsafe { Self::try_new(offset) }.expect("u32 overflow")
+    }
+
+    #[inline]
+    #[must_use]
+    pub unsafe fn try_new(offset: usize) -> Option<Self> {
+        u32::try_from(offset).ok().map(Self)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn into_offset(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl OffsetProvider<NonZeroU32> {
+    /// The highest offset that the provider can hold.
+    pub const MAX_OFFSET: usize = u32::MAX as usize - 1;
+
+    #[inline]
+    #[must_use]
+    pub unsafe fn new(offset: usize) -> Self {
+        unsafe { Self::try_new(offset) }.expect("u32 overflow")
+    }
+
+    #[inline]
+    #[must_use]
+    pub unsafe fn try_new(offset: usize) -> Option<Self> {
+        let inner = u32::try_from(offset).ok()?.checked_add(1)?;
+        Some(Self(unsafe { NonZeroU32::new_unchecked(inner) }))
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn into_offset(self) -> usize {
+        (self.0.get() - 1) as usize
+    }
+}
+
+/// Resolves the inner type of [`OffsetProvider`] from the `NicheOptimized`
+/// marker and the `Repr` section of the type-generating macros.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! offset_repr {
+    () => {
+        usize
+    };
+    (usize) => {
+        usize
+    };
+    (u32) => {
+        u32
+    };
+    (NicheOptimized) => {
+        ::core::num::NonZeroUsize
+    };
+    (NicheOptimized usize) => {
+        ::core::num::NonZeroUsize
+    };
+    (NicheOptimized u32) => {
+        ::core::num::NonZeroU32
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use std::{
         fmt::{Debug, Display},
-        num::NonZeroUsize,
+        num::{NonZeroU32, NonZeroUsize},
     };
 
     #[cfg(feature = "serde")]
@@ -64,9 +157,15 @@ mod tests {
     // generated by the [`define_key_wrapper`](crate::define_plain_id) macro.
     assert_not_impl_any!(OffsetProvider<usize>: Default, Debug, Display, PartialOrd);
     assert_not_impl_any!(OffsetProvider<NonZeroUsize>: Default, Debug, Display, PartialOrd);
+    assert_not_impl_any!(OffsetProvider<u32>: Default, Debug, Display, PartialOrd);
+    assert_not_impl_any!(OffsetProvider<NonZeroU32>: Default, Debug, Display, PartialOrd);
 
     #[cfg(feature = "serde")]
     assert_not_impl_any!(OffsetProvider<usize>: Serialize, Deserialize<'static>);
     #[cfg(feature = "serde")]
     assert_not_impl_any!(OffsetProvider<NonZeroUsize>: Serialize, Deserialize<'static>);
+    #[cfg(feature = "serde")]
+    assert_not_impl_any!(OffsetProvider<u32>: Serialize, Deserialize<'static>);
+    #[cfg(feature = "serde")]
+    assert_not_impl_any!(OffsetProvider<NonZeroU32>: Serialize, Deserialize<'static>);
 }
@@ -1,3 +1,4 @@
+//! Concurrency tests of the type-info containers driven by `loom`.
 #![cfg(feature = "loom")]
 
 use blazemap::{
@@ -1,3 +1,4 @@
+//! Randomized `BlazeMap` tests that are also suitable for running under Miri.
 #![cfg(all(not(feature = "loom"), feature = "serde"))]
 
 use crate::random_action::{ActionPeekWeights, EventWeights};
@@ -1,7 +1,9 @@
+//! Random action generators shared by the randomized `BlazeMap` tests.
 #![cfg(feature = "serde")]
 #![allow(renamed_and_removed_lints)]
 #![allow(illegal_floating_point_literal_pattern)]
 #![allow(missing_debug_implementations)]
+#![allow(missing_docs)]
 #![allow(unreachable_pub)]
 #![allow(clippy::missing_panics_doc)]
 #![allow(clippy::too_many_lines)]